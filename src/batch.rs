@@ -0,0 +1,285 @@
+use std::any::Any;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::thread;
+
+use serde::{Deserialize, Serialize};
+
+use crate::project::{ProjectError, RackProject};
+use crate::rack::{Container, GeneratedSvg};
+
+/// List of racks generated together. Saved as TOML with `[[racks]]` tables or as JSON.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BatchManifest {
+    pub racks: Vec<RackProject>,
+}
+
+/// Generated rack of a batch
+#[derive(Debug)]
+pub struct BatchRack {
+    pub project: RackProject,
+    /// File name without `.svg` extension, unique within the batch. `None` when the project
+    /// is invalid or its container can't be found.
+    pub filename: Option<String>,
+    pub result: Result<(Container, GeneratedSvg), ProjectError>,
+}
+
+#[derive(Debug)]
+pub enum BatchError {
+    Io(std::io::Error),
+    Toml(String),
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for BatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BatchError::Io(error) => write!(f, "Could not read manifest: {}", error),
+            BatchError::Toml(error) => write!(f, "Invalid TOML manifest: {}", error),
+            BatchError::Json(error) => write!(f, "Invalid JSON manifest: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for BatchError {}
+
+impl BatchManifest {
+    /// Load manifest from file, `.json` files are read as JSON and everything else as TOML
+    pub fn load(path: &Path) -> Result<BatchManifest, BatchError> {
+        let content = fs::read_to_string(path).map_err(BatchError::Io)?;
+        let is_json = path
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("json"));
+
+        if is_json {
+            serde_json::from_str(&content).map_err(BatchError::Json)
+        } else {
+            toml::from_str(&content).map_err(|e| BatchError::Toml(e.to_string()))
+        }
+    }
+
+    /// Generate every rack in parallel. Results are in manifest order.
+    ///
+    /// Projects are validated and their containers resolved before generation, invalid
+    /// racks and racks whose generation fails get an error without stopping the batch.
+    pub fn generate(&self) -> Vec<BatchRack> {
+        let containers: Vec<Result<Container, ProjectError>> = self
+            .racks
+            .iter()
+            .map(|project| project.validate().and_then(|_| project.resolve_container()))
+            .collect();
+        let filenames = self.filenames(&containers);
+
+        thread::scope(|scope| {
+            let handles: Vec<_> = self
+                .racks
+                .iter()
+                .zip(containers)
+                .map(|(project, container)| {
+                    container.map(|container| {
+                        scope.spawn(move || {
+                            let generated = project.generate_for(&container)?;
+                            Ok((container, generated))
+                        })
+                    })
+                })
+                .collect();
+
+            handles
+                .into_iter()
+                .zip(&self.racks)
+                .zip(filenames)
+                .map(|((handle, project), filename)| BatchRack {
+                    project: project.clone(),
+                    filename,
+                    result: handle.and_then(|handle| {
+                        handle
+                            .join()
+                            .unwrap_or_else(|panic| Err(ProjectError::Generation(panic_message(panic))))
+                    }),
+                })
+                .collect()
+        })
+    }
+
+    /// File names of the generated racks, `None` for racks that fail before generation.
+    /// Same names get the first running number that no other rack uses, so no output
+    /// overwrites another one.
+    fn filenames(&self, containers: &[Result<Container, ProjectError>]) -> Vec<Option<String>> {
+        let names: Vec<Option<String>> = self
+            .racks
+            .iter()
+            .zip(containers)
+            .map(|(project, container)| {
+                container.as_ref().ok().map(|container| project.filename(container))
+            })
+            .collect();
+
+        let mut totals: HashMap<&str, usize> = HashMap::new();
+        for name in names.iter().flatten() {
+            *totals.entry(name).or_default() += 1;
+        }
+        let mut taken: HashSet<String> = totals
+            .iter()
+            .filter(|(_, total)| **total == 1)
+            .map(|(name, _)| name.to_string())
+            .collect();
+
+        let mut counters: HashMap<&str, usize> = HashMap::new();
+        names
+            .iter()
+            .map(|name| {
+                let name = name.as_deref()?;
+                if totals[name] == 1 {
+                    return Some(name.to_string());
+                }
+                let counter = counters.entry(name).or_default();
+                let numbered = loop {
+                    *counter += 1;
+                    let numbered = format!("{}_{}", name, counter);
+                    if !taken.contains(&numbered) {
+                        break numbered;
+                    }
+                };
+                taken.insert(numbered.clone());
+                Some(numbered)
+            })
+            .collect()
+    }
+}
+
+/// Message of a panic, which is a string unless something else was given to `panic_any`
+fn panic_message(panic: Box<dyn Any + Send>) -> String {
+    match panic.downcast::<String>() {
+        Ok(message) => *message,
+        Err(panic) => match panic.downcast::<&str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "generation panicked".to_string(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalogue::{builtin_containers, find_container_in};
+    use crate::project::ProjectContainer;
+    use crate::units::Length;
+
+    fn manifest() -> BatchManifest {
+        let mut manifest: BatchManifest = toml::from_str(
+            r#"
+            [[racks]]
+            rows = 2
+            columns = 1
+            material_thickness = "4mm"
+            container = "classic 1"
+
+            [[racks]]
+            rows = 2
+            columns = 1
+            material_thickness = "4mm"
+            container = "smartstore-classic_1"
+
+            [[racks]]
+            rows = 1
+            columns = 2
+            material_thickness = "1/4in"
+            container = "samla 11l"
+            output = { filename = "samla" }
+            "#,
+        )
+        .unwrap();
+
+        // Built-in containers are given inline, so the user catalogue doesn't change the racks
+        for rack in &mut manifest.racks {
+            if let ProjectContainer::Key(key) = &rack.container {
+                let container = find_container_in(builtin_containers(), key).unwrap();
+                rack.container = ProjectContainer::Inline(container);
+            }
+        }
+        manifest
+    }
+
+    #[test]
+    fn test_generate_keeps_manifest_order() {
+        let racks = manifest().generate();
+
+        assert_eq!(racks.len(), 3);
+        let (container, generated) = racks[2].result.as_ref().unwrap();
+        assert_eq!(container.key(), "ikea-samla_11l");
+        assert_eq!(generated.assembled_dimensions.height, 140.0 + 2.0 * 6.35);
+    }
+
+    #[test]
+    fn test_duplicate_filenames_are_numbered() {
+        let racks = manifest().generate();
+        let filenames: Vec<&str> = racks.iter().flat_map(|rack| rack.filename.as_deref()).collect();
+
+        assert_eq!(
+            filenames,
+            [
+                "organizer_2_rows_1_columns_4mm_thick_smartstore-classic_1_1",
+                "organizer_2_rows_1_columns_4mm_thick_smartstore-classic_1_2",
+                "samla",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_numbered_filenames_do_not_collide_with_given_ones() {
+        let mut manifest = manifest();
+        for rack in &mut manifest.racks {
+            rack.output.filename = Some("rack".to_string());
+        }
+        manifest.racks[2].output.filename = Some("rack_1".to_string());
+
+        let racks = manifest.generate();
+        let filenames: Vec<&str> = racks.iter().flat_map(|rack| rack.filename.as_deref()).collect();
+
+        assert_eq!(filenames, ["rack_2", "rack_3", "rack_1"]);
+    }
+
+    #[test]
+    fn test_failing_rack_does_not_stop_batch() {
+        let mut manifest = manifest();
+        manifest.racks.push(RackProject::new(
+            1,
+            1,
+            Length::millimeters(4.0),
+            ProjectContainer::Key("wooden crate".to_string()),
+        ));
+
+        let racks = manifest.generate();
+
+        assert!(racks[..3].iter().all(|rack| rack.result.is_ok()));
+        assert!(racks[3].result.is_err());
+        assert_eq!(racks[3].filename, None);
+    }
+
+    #[test]
+    fn test_invalid_rack_is_an_error_row() {
+        let mut manifest = manifest();
+        manifest.racks[1].columns = 0;
+
+        let racks = manifest.generate();
+
+        assert!(racks[0].result.is_ok());
+        assert!(matches!(racks[1].result, Err(ProjectError::Invalid(_))));
+        assert_eq!(racks[1].filename, None);
+        assert_eq!(
+            racks[0].filename.as_deref(),
+            Some("organizer_2_rows_1_columns_4mm_thick_smartstore-classic_1")
+        );
+        assert!(racks[2].result.is_ok());
+    }
+
+    #[test]
+    fn test_panic_message() {
+        assert_eq!(panic_message(Box::new("boom")), "boom");
+        assert_eq!(panic_message(Box::new(format!("rack {}", 2))), "rack 2");
+        assert_eq!(panic_message(Box::new(42)), "generation panicked");
+    }
+}
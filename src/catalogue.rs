@@ -0,0 +1,704 @@
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::rack::{Container, ContainerDimensions, ContainerLink, ContainerProfile};
+
+const USER_CATALOGUE_DIRECTORY: &str = "container-rack";
+const USER_CATALOGUE_FILE: &str = "containers.toml";
+
+/// Containers sharing the same profile, so they can be mixed in the same rack
+#[derive(Debug, Clone)]
+pub struct ContainerFamily {
+    pub profile: ContainerProfile,
+    pub containers: Vec<Container>,
+}
+
+impl ContainerFamily {
+    /// Name built from the vendors and models of the family, e.g. `SmartStore Classic 1 / Classic 2`
+    pub fn name(&self) -> String {
+        let mut name = String::new();
+        let mut previous_vendor = None;
+
+        for container in &self.containers {
+            if !name.is_empty() {
+                name.push_str(" / ");
+            }
+            if previous_vendor != Some(&container.vendor) {
+                name.push_str(&container.vendor);
+                name.push(' ');
+                previous_vendor = Some(&container.vendor);
+            }
+            name.push_str(&container.model);
+        }
+
+        name
+    }
+}
+
+#[derive(Debug)]
+pub enum CatalogueError {
+    Io(std::io::Error),
+    Parse(toml::de::Error),
+    Serialize(toml::ser::Error),
+    NoConfigDirectory,
+}
+
+impl fmt::Display for CatalogueError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CatalogueError::Io(error) => write!(f, "Could not access user catalogue: {}", error),
+            CatalogueError::Parse(error) => write!(f, "Invalid user catalogue: {}", error),
+            CatalogueError::Serialize(error) => {
+                write!(f, "Could not serialize user catalogue: {}", error)
+            }
+            CatalogueError::NoConfigDirectory => {
+                write!(f, "Neither XDG_CONFIG_HOME nor HOME is set")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CatalogueError {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ContainerLookupError {
+    /// Nothing matched, suggestions are keys of the closest containers
+    NotFound { query: String, suggestions: Vec<String> },
+    /// Query matched several containers
+    Ambiguous { query: String, keys: Vec<String> },
+}
+
+impl fmt::Display for ContainerLookupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ContainerLookupError::NotFound { query, suggestions } if suggestions.is_empty() => {
+                write!(f, "No supported containers found for '{}'.", query)
+            }
+            ContainerLookupError::NotFound { query, suggestions } => write!(
+                f,
+                "No supported containers found for '{}'. Did you mean {}?",
+                query,
+                suggestions.join(", ")
+            ),
+            ContainerLookupError::Ambiguous { query, keys } => write!(
+                f,
+                "'{}' matches several containers: {}",
+                query,
+                keys.join(", ")
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ContainerLookupError {}
+
+/// File format of the user catalogue
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct UserCatalogue {
+    #[serde(default)]
+    containers: Vec<Container>,
+}
+
+/// All containers the library knows about: built-in containers and containers from the
+/// user catalogue. User containers replace built-in containers with the same key.
+///
+/// Unreadable user catalogue is ignored, use [`user_containers`] to get the error.
+pub fn supported_containers() -> Vec<Container> {
+    let mut containers = builtin_containers();
+    let user_containers = user_catalogue_path()
+        .and_then(|path| load_user_containers(&path).ok())
+        .unwrap_or_default();
+
+    for user_container in user_containers {
+        let key = user_container.key();
+        match containers.iter_mut().find(|c| c.key() == key) {
+            Some(container) => *container = user_container,
+            None => containers.push(user_container),
+        }
+    }
+
+    containers
+}
+
+/// Find supported container by key or alias, see [`find_container_in`]
+pub fn find_container(query: &str) -> Result<Container, ContainerLookupError> {
+    find_container_in(supported_containers(), query)
+}
+
+/// Find container by key, ignoring case, spaces, dashes and underscores. Vendor and model
+/// (`smartstore classic 1`) and model alone (`classic1`) work as aliases.
+///
+/// When nothing matches, the error suggests keys of containers closest to the query.
+pub fn find_container_in(
+    containers: Vec<Container>,
+    query: &str,
+) -> Result<Container, ContainerLookupError> {
+    if let Some(container) = containers.iter().find(|c| c.key() == query) {
+        return Ok(container.clone());
+    }
+
+    let normalized_query = normalize(query);
+    let full_name_matches: Vec<&Container> = containers
+        .iter()
+        .filter(|c| normalize(&c.key()) == normalized_query)
+        .collect();
+    let matches = if full_name_matches.is_empty() {
+        containers
+            .iter()
+            .filter(|c| normalize(&c.model) == normalized_query)
+            .collect()
+    } else {
+        full_name_matches
+    };
+
+    match matches.as_slice() {
+        [container] => Ok((*container).clone()),
+        [] => Err(ContainerLookupError::NotFound {
+            query: query.to_string(),
+            suggestions: suggestions(&containers, &normalized_query),
+        }),
+        _ => Err(ContainerLookupError::Ambiguous {
+            query: query.to_string(),
+            keys: matches.iter().map(|c| c.key()).collect(),
+        }),
+    }
+}
+
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Keys of containers whose aliases are within a few edits from the query, closest first
+fn suggestions(containers: &[Container], normalized_query: &str) -> Vec<String> {
+    let max_distance = (normalized_query.chars().count() / 3).max(2);
+    let mut candidates: Vec<(usize, String)> = containers
+        .iter()
+        .map(|container| {
+            let distance = [normalize(&container.key()), normalize(&container.model)]
+                .iter()
+                .map(|alias| edit_distance(alias, normalized_query))
+                .min()
+                .unwrap_or(usize::MAX);
+            (distance, container.key())
+        })
+        .filter(|(distance, _)| *distance <= max_distance)
+        .collect();
+    candidates.sort();
+
+    candidates
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, key)| key)
+        .collect()
+}
+
+/// Lowercase alphanumerics only, so `SmartStore Classic_1` equals `smartstore-classic-1`
+fn normalize(text: &str) -> String {
+    text.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Levenshtein distance
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+/// Path of the user catalogue: `$XDG_CONFIG_HOME/container-rack/containers.toml`,
+/// falling back to `~/.config/container-rack/containers.toml`
+pub fn user_catalogue_path() -> Option<PathBuf> {
+    let config_directory = match std::env::var_os("XDG_CONFIG_HOME") {
+        Some(directory) if !directory.is_empty() => PathBuf::from(directory),
+        _ => PathBuf::from(std::env::var_os("HOME")?).join(".config"),
+    };
+
+    Some(
+        config_directory
+            .join(USER_CATALOGUE_DIRECTORY)
+            .join(USER_CATALOGUE_FILE),
+    )
+}
+
+/// Containers from the user catalogue, empty if the catalogue does not exist yet
+pub fn user_containers() -> Result<Vec<Container>, CatalogueError> {
+    let path = user_catalogue_path().ok_or(CatalogueError::NoConfigDirectory)?;
+    load_user_containers(&path)
+}
+
+/// Containers from given catalogue file, empty if the file does not exist
+pub fn load_user_containers(path: &Path) -> Result<Vec<Container>, CatalogueError> {
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+
+    let content = fs::read_to_string(path).map_err(CatalogueError::Io)?;
+    let catalogue: UserCatalogue = toml::from_str(&content).map_err(CatalogueError::Parse)?;
+
+    Ok(catalogue.containers)
+}
+
+/// Add container to given catalogue file, replacing container with the same key
+pub fn save_user_container(path: &Path, container: &Container) -> Result<(), CatalogueError> {
+    let mut containers = load_user_containers(path)?;
+    let key = container.key();
+    match containers.iter_mut().find(|c| c.key() == key) {
+        Some(existing) => *existing = container.clone(),
+        None => containers.push(container.clone()),
+    }
+
+    let content =
+        toml::to_string_pretty(&UserCatalogue { containers }).map_err(CatalogueError::Serialize)?;
+    if let Some(directory) = path.parent() {
+        fs::create_dir_all(directory).map_err(CatalogueError::Io)?;
+    }
+
+    fs::write(path, content).map_err(CatalogueError::Io)
+}
+
+/// Containers shipped with the library
+///
+/// Dimensions are in mm. `height` is the height of one row, so boxes taller than the
+/// container a rack was generated for can still use the rack by taking several rows.
+pub fn builtin_containers() -> Vec<Container> {
+    vec![
+        Container {
+            vendor: "SmartStore".to_string(),
+            model: "Classic 1".to_string(),
+            description: "Rack generated with this container can also have classic 2 and classic 3 containers. And 'Home' versions of same containers.".to_string(),
+            links: vec![
+                link("SmartStore Classic 1", "https://www.orthexgroup.com/smartstore-classic/909-smartstore-classic-1-7310543520075.html"),
+                link("SmartStore Classic 2", "https://www.orthexgroup.com/smartstore-classic/918-smartstore-classic-2-7310543521072.html"),
+                link("SmartStore Classic 3", "https://www.orthexgroup.com/smartstore-classic/919-smartstore-classic-3-7310543522079.html"),
+            ],
+            dimensions: ContainerDimensions {
+                width: 170.0,
+                depth: 210.0,
+                height: 56.0,
+                side_wing_from_box_top: 15.0,
+                side_wing_width: 8.0,
+            },
+        },
+        Container {
+            vendor: "SmartStore".to_string(),
+            model: "Classic 2".to_string(),
+            description: "Same footprint and lip as Classic 1, so Classic 1 and 3 fit the rack too. 'Home' version has the same dimensions.".to_string(),
+            links: vec![link("SmartStore Classic 2", "https://www.orthexgroup.com/smartstore-classic/918-smartstore-classic-2-7310543521072.html")],
+            dimensions: ContainerDimensions {
+                width: 170.0,
+                depth: 210.0,
+                height: 86.0,
+                side_wing_from_box_top: 15.0,
+                side_wing_width: 8.0,
+            },
+        },
+        Container {
+            vendor: "SmartStore".to_string(),
+            model: "Classic 3".to_string(),
+            description: "Same footprint and lip as Classic 1, so Classic 1 and 2 fit the rack too. 'Home' version has the same dimensions.".to_string(),
+            links: vec![link("SmartStore Classic 3", "https://www.orthexgroup.com/smartstore-classic/919-smartstore-classic-3-7310543522079.html")],
+            dimensions: ContainerDimensions {
+                width: 170.0,
+                depth: 210.0,
+                height: 126.0,
+                side_wing_from_box_top: 15.0,
+                side_wing_width: 8.0,
+            },
+        },
+        Container {
+            vendor: "SmartStore".to_string(),
+            model: "Classic 10".to_string(),
+            description: "Larger Classic range. Classic 15 has the same footprint and lip and fits the rack too.".to_string(),
+            links: vec![link("SmartStore Classic", "https://www.orthexgroup.com/smartstore-classic/")],
+            dimensions: ContainerDimensions {
+                width: 290.0,
+                depth: 390.0,
+                height: 125.0,
+                side_wing_from_box_top: 17.0,
+                side_wing_width: 10.0,
+            },
+        },
+        Container {
+            vendor: "SmartStore".to_string(),
+            model: "Classic 15".to_string(),
+            description: "Larger Classic range. Classic 10 has the same footprint and lip and fits the rack too.".to_string(),
+            links: vec![link("SmartStore Classic", "https://www.orthexgroup.com/smartstore-classic/")],
+            dimensions: ContainerDimensions {
+                width: 290.0,
+                depth: 390.0,
+                height: 180.0,
+                side_wing_from_box_top: 17.0,
+                side_wing_width: 10.0,
+            },
+        },
+        Container {
+            vendor: "IKEA".to_string(),
+            model: "SAMLA 5L".to_string(),
+            description: "Small SAMLA box. Does not share footprint with the 11 and 22 liter boxes. Lids must be left off.".to_string(),
+            links: vec![link("IKEA SAMLA", "https://www.ikea.com/us/en/search/?q=samla")],
+            dimensions: ContainerDimensions {
+                width: 190.0,
+                depth: 280.0,
+                height: 140.0,
+                side_wing_from_box_top: 12.0,
+                side_wing_width: 10.0,
+            },
+        },
+        Container {
+            vendor: "IKEA".to_string(),
+            model: "SAMLA 11L".to_string(),
+            description: "SAMLA 22L has the same footprint and lip and fits the rack too. Lids must be left off.".to_string(),
+            links: vec![link("IKEA SAMLA", "https://www.ikea.com/us/en/search/?q=samla")],
+            dimensions: ContainerDimensions {
+                width: 280.0,
+                depth: 390.0,
+                height: 140.0,
+                side_wing_from_box_top: 12.0,
+                side_wing_width: 10.0,
+            },
+        },
+        Container {
+            vendor: "IKEA".to_string(),
+            model: "SAMLA 22L".to_string(),
+            description: "SAMLA 11L has the same footprint and lip and fits the rack too. Lids must be left off.".to_string(),
+            links: vec![link("IKEA SAMLA", "https://www.ikea.com/us/en/search/?q=samla")],
+            dimensions: ContainerDimensions {
+                width: 280.0,
+                depth: 390.0,
+                height: 280.0,
+                side_wing_from_box_top: 12.0,
+                side_wing_width: 10.0,
+            },
+        },
+        Container {
+            vendor: "Really Useful".to_string(),
+            model: "Box 4L".to_string(),
+            description: "Box slides in with the lid off. 9 liter box has the same footprint and lip and fits the rack too.".to_string(),
+            links: vec![link("Really Useful Products", "https://www.reallyusefulproducts.co.uk/")],
+            dimensions: ContainerDimensions {
+                width: 255.0,
+                depth: 395.0,
+                height: 88.0,
+                side_wing_from_box_top: 15.0,
+                side_wing_width: 8.0,
+            },
+        },
+        Container {
+            vendor: "Really Useful".to_string(),
+            model: "Box 9L".to_string(),
+            description: "Box slides in with the lid off. 4 liter box has the same footprint and lip and fits the rack too.".to_string(),
+            links: vec![link("Really Useful Products", "https://www.reallyusefulproducts.co.uk/")],
+            dimensions: ContainerDimensions {
+                width: 255.0,
+                depth: 395.0,
+                height: 155.0,
+                side_wing_from_box_top: 15.0,
+                side_wing_width: 8.0,
+            },
+        },
+        Container {
+            vendor: "Gratnells".to_string(),
+            model: "Shallow Tray F1".to_string(),
+            description: "Trays are designed to hang from runners, so all Gratnells tray depths fit the rack.".to_string(),
+            links: vec![link("Gratnells trays", "https://www.gratnells.com/")],
+            dimensions: ContainerDimensions {
+                width: 312.0,
+                depth: 427.0,
+                height: 75.0,
+                side_wing_from_box_top: 10.0,
+                side_wing_width: 10.0,
+            },
+        },
+        Container {
+            vendor: "Gratnells".to_string(),
+            model: "Deep Tray F2".to_string(),
+            description: "Trays are designed to hang from runners, so all Gratnells tray depths fit the rack.".to_string(),
+            links: vec![link("Gratnells trays", "https://www.gratnells.com/")],
+            dimensions: ContainerDimensions {
+                width: 312.0,
+                depth: 427.0,
+                height: 150.0,
+                side_wing_from_box_top: 10.0,
+                side_wing_width: 10.0,
+            },
+        },
+        Container {
+            vendor: "Gratnells".to_string(),
+            model: "Extra Deep Tray F25".to_string(),
+            description: "Trays are designed to hang from runners, so all Gratnells tray depths fit the rack.".to_string(),
+            links: vec![link("Gratnells trays", "https://www.gratnells.com/")],
+            dimensions: ContainerDimensions {
+                width: 312.0,
+                depth: 427.0,
+                height: 225.0,
+                side_wing_from_box_top: 10.0,
+                side_wing_width: 10.0,
+            },
+        },
+        Container {
+            vendor: "Euro".to_string(),
+            model: "Stacking Box 400x300x120".to_string(),
+            description: "Standard 400 x 300 mm euro container, inserted short side first. Other heights of the same footprint fit the rack too.".to_string(),
+            links: vec![link("Euro container", "https://en.wikipedia.org/wiki/Euro_container")],
+            dimensions: ContainerDimensions {
+                width: 300.0,
+                depth: 400.0,
+                height: 120.0,
+                side_wing_from_box_top: 10.0,
+                side_wing_width: 8.0,
+            },
+        },
+        Container {
+            vendor: "Euro".to_string(),
+            model: "Stacking Box 400x300x220".to_string(),
+            description: "Standard 400 x 300 mm euro container, inserted short side first. Other heights of the same footprint fit the rack too.".to_string(),
+            links: vec![link("Euro container", "https://en.wikipedia.org/wiki/Euro_container")],
+            dimensions: ContainerDimensions {
+                width: 300.0,
+                depth: 400.0,
+                height: 220.0,
+                side_wing_from_box_top: 10.0,
+                side_wing_width: 8.0,
+            },
+        },
+    ]
+}
+
+/// Supported containers grouped to families by their profile
+pub fn container_families() -> Vec<ContainerFamily> {
+    container_families_in(supported_containers())
+}
+
+/// Given containers grouped to families by their profile
+pub fn container_families_in(containers: Vec<Container>) -> Vec<ContainerFamily> {
+    let mut families: Vec<ContainerFamily> = Vec::new();
+
+    for container in containers {
+        let profile = container.dimensions.profile();
+        match families
+            .iter_mut()
+            .find(|family| family.profile.matches(&profile))
+        {
+            Some(family) => family.containers.push(container),
+            None => families.push(ContainerFamily {
+                profile,
+                containers: vec![container],
+            }),
+        }
+    }
+
+    families
+}
+
+/// Supported containers that fit a rack generated for container with given key, see
+/// [`compatible_containers_in`]
+pub fn compatible_containers(key: &str) -> Option<Vec<Container>> {
+    compatible_containers_in(supported_containers(), key)
+}
+
+/// Containers that fit a rack generated for container with given key, including the
+/// container itself. `None` if there is no container with the key.
+pub fn compatible_containers_in(containers: Vec<Container>, key: &str) -> Option<Vec<Container>> {
+    let rack_container = containers.iter().find(|c| c.key() == key)?;
+
+    Some(
+        containers
+            .iter()
+            .filter(|c| c.fits_rack_for(rack_container))
+            .cloned()
+            .collect(),
+    )
+}
+
+fn link(title: &str, url: &str) -> ContainerLink {
+    ContainerLink {
+        url: Url::parse(url).unwrap(),
+        title: title.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate_svg;
+    use crate::units::Unit;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_keys_are_unique() {
+        let containers = builtin_containers();
+        let keys: HashSet<String> = containers.iter().map(|c| c.key()).collect();
+
+        assert_eq!(keys.len(), containers.len());
+    }
+
+    #[test]
+    fn test_every_container_has_links() {
+        for container in builtin_containers() {
+            assert!(!container.links.is_empty(), "{} has no links", container.key());
+        }
+    }
+
+    #[test]
+    fn test_rack_can_be_generated_for_every_container() {
+        for container in builtin_containers() {
+            for (rows, columns) in [(1, 1), (3, 2), (8, 3)] {
+                let result = generate_svg(rows, columns, 4.0, &container, "black", "blue", Unit::Millimeter);
+
+                assert!(
+                    result.assembled_dimensions.width > container.dimensions.width * columns as f32,
+                    "{} rack is narrower than its containers",
+                    container.key()
+                );
+                assert_eq!(result.assembled_dimensions.depth, container.dimensions.depth);
+            }
+        }
+    }
+
+    #[test]
+    fn test_smartstore_classic_family() {
+        let family = container_families_in(builtin_containers())
+            .into_iter()
+            .find(|family| family.containers.iter().any(|c| c.key() == "smartstore-classic_1"))
+            .unwrap();
+
+        assert_eq!(family.name(), "SmartStore Classic 1 / Classic 2 / Classic 3");
+    }
+
+    #[test]
+    fn test_every_container_belongs_to_one_family() {
+        let containers_in_families: usize = container_families_in(builtin_containers())
+            .iter()
+            .map(|family| family.containers.len())
+            .sum();
+
+        assert_eq!(containers_in_families, builtin_containers().len());
+    }
+
+    #[test]
+    fn test_compatible_containers() {
+        let keys: Vec<String> = compatible_containers_in(builtin_containers(), "smartstore-classic_2")
+            .unwrap()
+            .iter()
+            .map(|c| c.key())
+            .collect();
+
+        assert_eq!(
+            keys,
+            vec!["smartstore-classic_1", "smartstore-classic_2", "smartstore-classic_3"]
+        );
+        assert!(compatible_containers_in(builtin_containers(), "unknown").is_none());
+    }
+
+    #[test]
+    fn test_builtin_dimensions_are_valid() {
+        for container in builtin_containers() {
+            assert!(container.dimensions.validate().is_ok(), "{}", container.key());
+        }
+    }
+
+    #[test]
+    fn test_save_and_load_user_containers() {
+        let path = std::env::temp_dir()
+            .join(format!("container-rack-test-{}", std::process::id()))
+            .join(USER_CATALOGUE_FILE);
+        let mut container = builtin_containers().remove(0);
+        container.vendor = "Custom".to_string();
+        container.dimensions.width = 171.5;
+
+        assert!(load_user_containers(&path).unwrap().is_empty());
+
+        save_user_container(&path, &container).unwrap();
+        container.description = "Updated".to_string();
+        save_user_container(&path, &container).unwrap();
+        let loaded = load_user_containers(&path).unwrap();
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].key(), "custom-classic_1");
+        assert_eq!(loaded[0].description, "Updated");
+        assert_eq!(loaded[0].dimensions.width, 171.5);
+        assert_eq!(loaded[0].links.len(), container.links.len());
+    }
+
+    #[test]
+    fn test_find_container_by_key_and_aliases() {
+        for query in [
+            "smartstore-classic_1",
+            "SmartStore-Classic_1",
+            "smartstore classic 1",
+            "smartstore-classic-1",
+            "classic1",
+            "Classic 1",
+        ] {
+            let container = find_container_in(builtin_containers(), query).unwrap();
+            assert_eq!(container.key(), "smartstore-classic_1", "query: {}", query);
+        }
+    }
+
+    #[test]
+    fn test_find_container_suggests_closest() {
+        let error = find_container_in(builtin_containers(), "smartstore-clasic_2").unwrap_err();
+
+        match error {
+            ContainerLookupError::NotFound { suggestions, .. } => {
+                assert_eq!(suggestions[0], "smartstore-classic_2");
+                assert!(suggestions.len() <= MAX_SUGGESTIONS);
+            }
+            _ => panic!("Expected not found, got {:?}", error),
+        }
+    }
+
+    #[test]
+    fn test_find_container_without_suggestions() {
+        let error = find_container_in(builtin_containers(), "wooden crate").unwrap_err();
+
+        assert_eq!(
+            error,
+            ContainerLookupError::NotFound {
+                query: "wooden crate".to_string(),
+                suggestions: vec![],
+            }
+        );
+    }
+
+    #[test]
+    fn test_find_container_ambiguous_model() {
+        let mut containers = builtin_containers();
+        let mut other = containers[0].clone();
+        other.vendor = "Other".to_string();
+        containers.push(other);
+
+        let error = find_container_in(containers, "classic 1").unwrap_err();
+
+        assert_eq!(
+            error,
+            ContainerLookupError::Ambiguous {
+                query: "classic 1".to_string(),
+                keys: vec!["smartstore-classic_1".to_string(), "other-classic_1".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("", ""), 0);
+        assert_eq!(edit_distance("classic", "clasic"), 1);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("abc", ""), 3);
+    }
+}
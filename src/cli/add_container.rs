@@ -0,0 +1,208 @@
+use std::io::{self, BufRead, Write};
+use std::str::FromStr;
+
+use container_rack_lib::catalogue::{save_user_container, user_catalogue_path};
+use container_rack_lib::rack::{Container, ContainerDimensions, ContainerLink};
+use container_rack_lib::units::Length;
+use url::Url;
+
+use crate::cli::cross_section::ascii_cross_section;
+
+/// Ask container measurements step by step and save the container to the user catalogue
+pub fn add_container() {
+    let path = match user_catalogue_path() {
+        Some(path) => path,
+        None => {
+            println!("Cannot find config directory, set XDG_CONFIG_HOME or HOME.");
+            std::process::exit(1);
+        }
+    };
+
+    let stdin = io::stdin();
+    let mut input = stdin.lock();
+    let mut output = io::stdout();
+
+    let container = match ask_container(&mut input, &mut output) {
+        Ok(Some(container)) => container,
+        Ok(None) => {
+            println!("Container was not saved.");
+            return;
+        }
+        Err(error) => {
+            println!("Could not read answer: {}", error);
+            std::process::exit(1);
+        }
+    };
+
+    match save_user_container(&path, &container) {
+        Ok(()) => println!(
+            "Saved {} to {}",
+            container.key(),
+            path.to_string_lossy()
+        ),
+        Err(error) => {
+            println!("{}", error);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Run the wizard. Returns `None` if the user did not confirm saving.
+fn ask_container(input: &mut impl BufRead, output: &mut impl Write) -> io::Result<Option<Container>> {
+    writeln!(output, "Let's measure your container. Lengths are in mm unless you add a unit, e.g. 1/4in.")?;
+    writeln!(output)?;
+
+    let vendor = ask_text(input, output, "Brand of the container", false)?;
+    let model = ask_text(input, output, "Model of the container", false)?;
+    let description = ask_text(input, output, "Description, e.g. which other boxes fit (optional)", true)?;
+    let url = ask(input, output, "Link to product page (optional)", |answer| {
+        if answer.is_empty() {
+            Ok(None)
+        } else {
+            Url::parse(answer).map(Some).map_err(|e| e.to_string())
+        }
+    })?;
+
+    writeln!(output)?;
+    writeln!(output, "Measure the widest part of the box, usually the lip around the top.")?;
+    let width = ask_length(input, output, "Width")?;
+    writeln!(output, "Depth is measured front to back, the direction the box slides into the rack.")?;
+    let depth = ask_length(input, output, "Depth")?;
+    writeln!(output, "Height is the space one box takes. Taller boxes of the same family can take several rows.")?;
+    let height = ask_length(input, output, "Height")?;
+    writeln!(output, "The box hangs from its lip on side wings. Measure from the top of the box to the underside of the lip.")?;
+    let side_wing_from_box_top = ask_length(input, output, "Side wing from box top")?;
+    writeln!(output, "Measure how far the lip sticks out from the side of the box. Wings can't be wider than that.")?;
+    let side_wing_width = ask_length(input, output, "Side wing width")?;
+
+    let dimensions = ContainerDimensions {
+        width,
+        depth,
+        height,
+        side_wing_from_box_top,
+        side_wing_width,
+    };
+
+    writeln!(output)?;
+    writeln!(output, "{}", ascii_cross_section(&dimensions))?;
+    writeln!(output)?;
+
+    if let Err(problems) = dimensions.validate() {
+        writeln!(output, "These measurements won't work:")?;
+        for problem in problems {
+            writeln!(output, "  - {}", problem)?;
+        }
+        return Ok(None);
+    }
+
+    let confirmed = ask(input, output, "Save container? [y/N]", |answer| {
+        Ok::<bool, String>(matches!(answer.to_lowercase().as_str(), "y" | "yes"))
+    })?;
+
+    if !confirmed {
+        return Ok(None);
+    }
+
+    Ok(Some(Container {
+        links: url
+            .map(|url| {
+                vec![ContainerLink {
+                    url,
+                    title: format!("{} {}", vendor, model),
+                }]
+            })
+            .unwrap_or_default(),
+        vendor,
+        model,
+        description,
+        dimensions,
+    }))
+}
+
+fn ask_text(input: &mut impl BufRead, output: &mut impl Write, question: &str, optional: bool) -> io::Result<String> {
+    ask(input, output, question, |answer| {
+        if answer.is_empty() && !optional {
+            Err("Answer is required".to_string())
+        } else {
+            Ok(answer.to_string())
+        }
+    })
+}
+
+fn ask_length(input: &mut impl BufRead, output: &mut impl Write, question: &str) -> io::Result<f32> {
+    ask(input, output, question, |answer| {
+        let length = Length::from_str(answer)?;
+        if length.value == 0.0 {
+            Err("Length must be more than zero".to_string())
+        } else {
+            Ok(length.to_mm())
+        }
+    })
+}
+
+/// Ask until the answer parses, fails if input ends
+fn ask<T, E: std::fmt::Display>(
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+    question: &str,
+    parse: impl Fn(&str) -> Result<T, E>,
+) -> io::Result<T> {
+    loop {
+        write!(output, "{}: ", question)?;
+        output.flush()?;
+
+        let mut answer = String::new();
+        if input.read_line(&mut answer)? == 0 {
+            return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "input ended"));
+        }
+
+        match parse(answer.trim()) {
+            Ok(value) => return Ok(value),
+            Err(error) => writeln!(output, "{}", error)?,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_wizard_creates_container() {
+        let answers = "Acme\nCrate 1\n\nnot a url\nhttps://example.com/crate\n170.5\n210\n56\n15\n0\n5/16in\ny\n";
+        let mut output = Vec::new();
+
+        let container = ask_container(&mut Cursor::new(answers), &mut output)
+            .unwrap()
+            .unwrap();
+        let output = String::from_utf8(output).unwrap();
+
+        assert_eq!(container.key(), "acme-crate_1");
+        assert_eq!(container.links[0].url.as_str(), "https://example.com/crate");
+        assert_eq!(container.dimensions.width, 170.5);
+        assert_eq!(container.dimensions.side_wing_width, 7.9375);
+        assert!(output.contains("relative URL without a base"));
+        assert!(output.contains("Length must be more than zero"));
+        assert!(output.contains("side wings 7.9375mm wide"));
+    }
+
+    #[test]
+    fn test_wizard_rejects_invalid_measurements() {
+        let answers = "Acme\nCrate 1\n\n\n170\n100\n56\n15\n8\n";
+        let mut output = Vec::new();
+
+        let container = ask_container(&mut Cursor::new(answers), &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+
+        assert!(container.is_none());
+        assert!(output.contains("Depth must be at least 150mm"));
+    }
+
+    #[test]
+    fn test_wizard_fails_when_input_ends() {
+        let mut output = Vec::new();
+
+        assert!(ask_container(&mut Cursor::new("Acme\n"), &mut output).is_err());
+    }
+}
@@ -0,0 +1,164 @@
+use std::path::PathBuf;
+
+use clap::Args;
+use comfy_table::{Cell, Table};
+use container_rack_lib::batch::{BatchManifest, BatchRack};
+
+use crate::cli::format_length;
+
+#[derive(Args, Debug)]
+pub struct BatchArgs {
+    /// Manifest (TOML or JSON) listing the racks as `[[racks]]` project tables
+    manifest: PathBuf,
+
+    /// Directory to save the SVGs to
+    #[arg(short, long, default_value = ".")]
+    output_dir: PathBuf,
+}
+
+/// Generate every rack of the manifest and print a summary
+pub fn run(args: &BatchArgs) {
+    let manifest = match BatchManifest::load(&args.manifest) {
+        Ok(manifest) => manifest,
+        Err(error) => {
+            println!("{}", error);
+            std::process::exit(1);
+        }
+    };
+    if let Err(error) = std::fs::create_dir_all(&args.output_dir) {
+        println!("Could not create {}: {}", args.output_dir.to_string_lossy(), error);
+        std::process::exit(1);
+    }
+
+    let racks = manifest.generate();
+    let mut failed = false;
+    for rack in &racks {
+        if let (Ok((_, generated)), Some(filename)) = (&rack.result, &rack.filename) {
+            let path = args.output_dir.join(format!("{}.svg", filename));
+            if let Err(error) = svg::save(&path, &generated.document) {
+                println!("Could not save {}: {}", path.to_string_lossy(), error);
+                failed = true;
+            }
+        } else {
+            failed = true;
+        }
+    }
+
+    println!("{}", summary_table(&racks));
+    println!("Saved to {}", args.output_dir.to_string_lossy());
+
+    if failed {
+        std::process::exit(1);
+    }
+}
+
+fn summary_table(racks: &[BatchRack]) -> Table {
+    let mut table = Table::new();
+    table.set_header(vec![
+        Cell::new("File"),
+        Cell::new("Container"),
+        Cell::new("Rows x Columns"),
+        Cell::new("Material"),
+        Cell::new("Assembled (W x H x D)"),
+        Cell::new("Sheet (W x H)"),
+        Cell::new("Material use"),
+    ]);
+
+    let mut total_area = 0.0;
+    for rack in racks {
+        let project = &rack.project;
+        let mut row = vec![
+            Cell::new(match (&rack.result, &rack.filename) {
+                (Ok(_), Some(filename)) => format!("{}.svg", filename),
+                _ => "-".to_string(),
+            }),
+            Cell::new(""),
+            Cell::new(format!("{} x {}", project.rows, project.columns)),
+            Cell::new(project.material_thickness),
+        ];
+
+        match &rack.result {
+            Ok((container, generated)) => {
+                let unit = project.output.document_unit();
+                let assembled = generated.assembled_dimensions.to_unit(unit);
+                let sheet = generated.sheet_dimensions;
+                total_area += sheet.area();
+                row[1] = Cell::new(container.key());
+                row.push(Cell::new(format!(
+                    "{} x {} x {}",
+                    format_length(assembled.width, unit),
+                    format_length(assembled.height, unit),
+                    format_length(assembled.depth, unit)
+                )));
+                row.push(Cell::new(format!(
+                    "{} x {}",
+                    format_length(unit.from_mm(sheet.width), unit),
+                    format_length(unit.from_mm(sheet.height), unit)
+                )));
+                row.push(Cell::new(format_area(sheet.area())));
+            }
+            Err(error) => {
+                row.push(Cell::new(format!("Failed: {}", error)));
+            }
+        }
+
+        table.add_row(row);
+    }
+
+    table.add_row(vec![
+        Cell::new("Total"),
+        Cell::new(""),
+        Cell::new(""),
+        Cell::new(""),
+        Cell::new(""),
+        Cell::new(""),
+        Cell::new(format_area(total_area)),
+    ]);
+
+    table
+}
+
+/// Area given in mm² as m²
+fn format_area(area: f32) -> String {
+    format!("{:.3}m²", area / 1_000_000.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use container_rack_lib::catalogue::{builtin_containers, find_container_in};
+    use container_rack_lib::project::{ProjectContainer, RackProject};
+    use container_rack_lib::units::Length;
+
+    #[test]
+    fn test_summary_table() {
+        let manifest = BatchManifest {
+            racks: vec![
+                RackProject::new(
+                    1,
+                    1,
+                    Length::millimeters(3.0),
+                    ProjectContainer::Inline(
+                        find_container_in(builtin_containers(), "smartstore-classic_1").unwrap(),
+                    ),
+                ),
+                RackProject::new(
+                    1,
+                    1,
+                    Length::millimeters(3.0),
+                    ProjectContainer::Key("wooden crate".to_string()),
+                ),
+            ],
+        };
+
+        let table = summary_table(&manifest.generate()).to_string();
+
+        assert!(table.contains("smartstore-classic_1"));
+        assert!(table.contains("180.0mm x 62.0mm x 210.0mm"));
+        assert!(table.contains("461.0mm x 426.0mm"));
+        assert!(table.contains("0.196m²"));
+        assert!(table.contains("Failed: "));
+        assert!(!table.contains("rack_2.svg"));
+        assert!(table.contains("| - "));
+    }
+}
@@ -1,10 +1,66 @@
+use clap::{Args, Subcommand, ValueEnum};
 use comfy_table::{Cell, Table};
-use container_rack_lib::rack::Container;
+use container_rack_lib::catalogue::{compatible_containers, find_container};
+use container_rack_lib::rack::{Container, ContainerDimensions, ContainerLink};
 use container_rack_lib::supported_containers;
+use serde::Serialize;
+
+use crate::cli::add_container;
+use crate::cli::cross_section::ascii_cross_section;
+
+#[derive(Args, Debug)]
+pub struct ContainersArgs {
+    #[command(subcommand)]
+    command: Option<ContainersCommand>,
+
+    /// Output format of the container list
+    #[arg(short, long, value_enum, default_value_t = OutputFormat::Table)]
+    output: OutputFormat,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+enum OutputFormat {
+    Table,
+    Json,
+    Yaml,
+    Csv,
+}
+
+#[derive(Subcommand, Debug)]
+enum ContainersCommand {
+    /// List containers that fit a rack generated for given container
+    Compatible {
+        /// Key of container the rack is generated for
+        key: String,
+    },
+    /// Measure a new container step by step and save it to the user catalogue
+    Add,
+    /// Show all details and a cross-section of a container
+    Show {
+        /// Key of container
+        key: String,
+    },
+}
+
+pub fn run(args: &ContainersArgs) {
+    match &args.command {
+        None => print_containers(args.output),
+        Some(ContainersCommand::Compatible { key }) => {
+            print_compatible_containers(key, args.output)
+        }
+        Some(ContainersCommand::Add) => add_container::add_container(),
+        Some(ContainersCommand::Show { key }) => print_container_details(key),
+    }
+}
 
 /// Print containers to CLI
-pub fn print_containers() {
+fn print_containers(format: OutputFormat) {
     let containers = supported_containers();
+    if format != OutputFormat::Table {
+        print_structured(&containers, format);
+        return;
+    }
+
     let mut table = Table::new();
     table.set_header(table_header());
 
@@ -15,6 +71,195 @@ pub fn print_containers() {
     println!("{table}");
 }
 
+/// Print containers that fit a rack generated for given container
+fn print_compatible_containers(key: &str, format: OutputFormat) {
+    let rack_container = match find_container(key) {
+        Ok(container) => container,
+        Err(error) => {
+            println!("{}", error);
+            std::process::exit(1);
+        }
+    };
+    let compatible = compatible_containers(&rack_container.key()).unwrap_or_default();
+    if format != OutputFormat::Table {
+        print_structured(&compatible, format);
+        return;
+    }
+
+    let mut table = Table::new();
+    table.set_header(vec![
+        Cell::new("Key"),
+        Cell::new("Brand"),
+        Cell::new("Model"),
+        Cell::new("Height"),
+        Cell::new("Rows needed"),
+    ]);
+
+    compatible.iter().for_each(|container| {
+        table.add_row(vec![
+            Cell::new(container.key()),
+            Cell::new(&container.vendor),
+            Cell::new(&container.model),
+            Cell::new(format!("{}mm", container.dimensions.height)),
+            Cell::new(container.rows_needed_in_rack_for(&rack_container)),
+        ]);
+    });
+
+    println!(
+        "Containers fitting a rack generated for {} {}:",
+        rack_container.vendor, rack_container.model
+    );
+    println!("{table}");
+}
+
+/// Print every detail of a container
+fn print_container_details(key: &str) {
+    match find_container(key) {
+        Ok(container) => println!("{}", container_details(&container)),
+        Err(error) => {
+            println!("{}", error);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn container_details(container: &Container) -> String {
+    let dimensions = &container.dimensions;
+    let mut table = Table::new();
+    table.set_header(vec![Cell::new("Dimension"), Cell::new("mm")]);
+    for (name, value) in [
+        ("Width", dimensions.width),
+        ("Depth", dimensions.depth),
+        ("Height", dimensions.height),
+        ("Side wing from box top", dimensions.side_wing_from_box_top),
+        ("Side wing width", dimensions.side_wing_width),
+    ] {
+        table.add_row(vec![Cell::new(name), Cell::new(value)]);
+    }
+
+    let mut details = vec![
+        format!("{} {} ({})", container.vendor, container.model, container.key()),
+        container.description.clone(),
+        String::new(),
+        table.to_string(),
+        String::new(),
+    ];
+
+    if !container.links.is_empty() {
+        details.push("Links:".to_string());
+        details.extend(
+            container
+                .links
+                .iter()
+                .map(|link| format!("  {}: {}", link.title, link.url)),
+        );
+        details.push(String::new());
+    }
+
+    details.push("Cross-section:".to_string());
+    details.push(ascii_cross_section(dimensions));
+
+    details.join("\n")
+}
+
+/// Every field of a container, including its key
+#[derive(Serialize)]
+struct ContainerRecord<'a> {
+    key: String,
+    vendor: &'a str,
+    model: &'a str,
+    description: &'a str,
+    dimensions: &'a ContainerDimensions,
+    links: &'a [ContainerLink],
+}
+
+impl<'a> From<&'a Container> for ContainerRecord<'a> {
+    fn from(container: &'a Container) -> Self {
+        ContainerRecord {
+            key: container.key(),
+            vendor: &container.vendor,
+            model: &container.model,
+            description: &container.description,
+            dimensions: &container.dimensions,
+            links: &container.links,
+        }
+    }
+}
+
+/// CSV can't nest, so dimensions get own columns and links are joined
+#[derive(Serialize)]
+struct ContainerCsvRecord<'a> {
+    key: String,
+    vendor: &'a str,
+    model: &'a str,
+    description: &'a str,
+    width: f32,
+    depth: f32,
+    height: f32,
+    side_wing_from_box_top: f32,
+    side_wing_width: f32,
+    link_titles: String,
+    link_urls: String,
+}
+
+impl<'a> From<&'a Container> for ContainerCsvRecord<'a> {
+    fn from(container: &'a Container) -> Self {
+        ContainerCsvRecord {
+            key: container.key(),
+            vendor: &container.vendor,
+            model: &container.model,
+            description: &container.description,
+            width: container.dimensions.width,
+            depth: container.dimensions.depth,
+            height: container.dimensions.height,
+            side_wing_from_box_top: container.dimensions.side_wing_from_box_top,
+            side_wing_width: container.dimensions.side_wing_width,
+            link_titles: container
+                .links
+                .iter()
+                .map(|link| link.title.as_str())
+                .collect::<Vec<&str>>()
+                .join("; "),
+            link_urls: container
+                .links
+                .iter()
+                .map(|link| link.url.as_str())
+                .collect::<Vec<&str>>()
+                .join(" "),
+        }
+    }
+}
+
+fn print_structured(containers: &[Container], format: OutputFormat) {
+    match render_structured(containers, format) {
+        Ok(output) => print!("{}", output),
+        Err(error) => {
+            println!("Could not format containers: {}", error);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn render_structured(
+    containers: &[Container],
+    format: OutputFormat,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let records: Vec<ContainerRecord> = containers.iter().map(ContainerRecord::from).collect();
+
+    match format {
+        OutputFormat::Json => Ok(serde_json::to_string_pretty(&records)? + "\n"),
+        OutputFormat::Yaml => Ok(serde_yaml::to_string(&records)?),
+        OutputFormat::Csv => {
+            let mut writer = csv::Writer::from_writer(vec![]);
+            for container in containers {
+                writer.serialize(ContainerCsvRecord::from(container))?;
+            }
+            Ok(String::from_utf8(writer.into_inner()?)?)
+        }
+        OutputFormat::Table => Err("table is not a structured format".into()),
+    }
+}
+
 fn table_header() -> Vec<Cell> {
     vec![
         Cell::new("Key"),
@@ -32,13 +277,12 @@ trait ToTableRow {
 impl ToTableRow for Container {
     fn to_table_row(&self) -> Vec<Cell> {
         vec![
-            Cell::new(&self.key()),
+            Cell::new(self.key()),
             Cell::new(&self.vendor),
             Cell::new(&self.model),
             Cell::new(&self.description).truncate(40),
             Cell::new(
-                &self
-                    .links
+                self.links
                     .iter()
                     .map(|link| link.url.to_string())
                     .collect::<Vec<String>>()
@@ -91,3 +335,63 @@ impl TruncateWords for &str {
         chunks
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use container_rack_lib::catalogue::builtin_containers;
+
+    #[test]
+    fn test_json_output_has_every_field() {
+        let containers = builtin_containers();
+
+        let output = render_structured(&containers[..1], OutputFormat::Json).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+        let container = &parsed[0];
+
+        assert_eq!(container["key"], "smartstore-classic_1");
+        assert_eq!(container["vendor"], "SmartStore");
+        assert_eq!(container["description"], containers[0].description);
+        assert_eq!(container["dimensions"]["side_wing_width"], 8.0);
+        assert_eq!(container["links"].as_array().unwrap().len(), 3);
+        assert_eq!(container["links"][2]["title"], "SmartStore Classic 3");
+    }
+
+    #[test]
+    fn test_container_details() {
+        let container = &builtin_containers()[0];
+
+        let details = container_details(container);
+
+        assert!(details.starts_with("SmartStore Classic 1 (smartstore-classic_1)\n"));
+        assert!(details.contains("Side wing from box top"));
+        assert!(details.contains("  SmartStore Classic 3: https://www.orthexgroup.com/"));
+        assert!(details.contains("side wings 8mm wide"));
+    }
+
+    #[test]
+    fn test_yaml_output() {
+        let containers = builtin_containers();
+
+        let output = render_structured(&containers[..1], OutputFormat::Yaml).unwrap();
+
+        assert!(output.starts_with("- key: smartstore-classic_1\n"));
+        assert!(output.contains("  side_wing_from_box_top: 15.0\n"));
+    }
+
+    #[test]
+    fn test_csv_output() {
+        let containers = builtin_containers();
+
+        let output = render_structured(&containers[..2], OutputFormat::Csv).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(
+            lines[0],
+            "key,vendor,model,description,width,depth,height,side_wing_from_box_top,side_wing_width,link_titles,link_urls"
+        );
+        assert!(lines[1].starts_with("smartstore-classic_1,SmartStore,Classic 1,"));
+        assert!(lines[1].contains(",170.0,210.0,56.0,15.0,8.0,SmartStore Classic 1; SmartStore Classic 2; SmartStore Classic 3,"));
+    }
+}
@@ -0,0 +1,49 @@
+use container_rack_lib::rack::ContainerDimensions;
+
+/// Cross-section of a box hanging from the side wings, seen from the front. Not in scale.
+pub fn ascii_cross_section(dimensions: &ContainerDimensions) -> String {
+    [
+        format!("          width {}mm", dimensions.width),
+        "   |<------------------->|".to_string(),
+        "   +---------------------+   ---  top of the box".to_string(),
+        format!(
+            "   |__                 __|    |   side wing {}mm from box top",
+            dimensions.side_wing_from_box_top
+        ),
+        format!(
+            "#####|               |#####  ---  side wings {}mm wide under the lip",
+            dimensions.side_wing_width
+        ),
+        "     |               |        |".to_string(),
+        format!(
+            "     |               |        |   height {}mm",
+            dimensions.height
+        ),
+        "     |               |        |".to_string(),
+        "     +---------------+       ---".to_string(),
+    ]
+    .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cross_section_shows_dimensions() {
+        let dimensions = ContainerDimensions {
+            width: 170.5,
+            depth: 210.0,
+            height: 56.0,
+            side_wing_from_box_top: 15.0,
+            side_wing_width: 7.6,
+        };
+
+        let diagram = ascii_cross_section(&dimensions);
+
+        assert!(diagram.contains("width 170.5mm"));
+        assert!(diagram.contains("15mm from box top"));
+        assert!(diagram.contains("7.6mm wide"));
+        assert!(diagram.contains("height 56mm"));
+    }
+}
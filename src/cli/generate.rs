@@ -1,93 +1,389 @@
+use std::path::PathBuf;
+
 use clap::Parser;
-use container_rack_lib::{generate_svg, supported_containers};
+use container_rack_lib::catalogue::find_container;
+use container_rack_lib::color::{Color, Palette};
+use container_rack_lib::project::{ProjectColors, ProjectContainer, ProjectError, RackProject};
+use container_rack_lib::rack::stats::LayoutStats;
+use container_rack_lib::estimate::{estimate_job, JobEstimate, MachineProfile};
+use container_rack_lib::rack::output::LaserSoftware;
+use container_rack_lib::rack::tabs::MicroTabs;
+use container_rack_lib::rack::{
+    part_sizes, AssembledDimensions, Container, GeneratedSvg, PartKind, SheetDimensions,
+};
+use container_rack_lib::sheets::pack;
+use container_rack_lib::units::{Length, Unit};
+
+use crate::cli::format_length;
 
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
 pub struct RackGenerationArgs {
     /// Number of rows of boxes
-    #[arg(short, long)]
-    rows: usize,
+    #[arg(short, long, required_unless_present = "project")]
+    rows: Option<usize>,
 
     /// Number columns of boxes
-    #[arg(short, long)]
-    columns: usize,
+    #[arg(short, long, required_unless_present = "project")]
+    columns: Option<usize>,
 
-    /// Thickness of the plywood or other material
-    #[arg(short, long)]
-    material_thickness: f32,
+    /// Thickness of the plywood or other material, e.g. 4, 4mm or 1/4in. Defaults to mm
+    #[arg(short, long, required_unless_present = "project")]
+    material_thickness: Option<Length>,
 
-    /// Key of container
-    #[arg(long)]
-    container: String,
+    /// Key of container, e.g. smartstore-classic_1. Case, spaces and dashes are ignored,
+    /// vendor and model like "smartstore classic 1" or model alone like classic1 work too
+    #[arg(long, required_unless_present = "project")]
+    container: Option<String>,
 
     /// Name of the file to save the SVG to
     #[arg(short, long)]
     output_filename: Option<String>,
 
-    /// Primary color of the line that will be cut first
-    #[clap(short, long, default_value = "black")]
-    primary_color: String,
+    /// Primary color of the line that will be cut first: CSS color name, #rgb, #rrggbb,
+    /// rgb(r, g, b) or palette color like lightburn:00 [default: black]
+    #[clap(short, long)]
+    primary_color: Option<Color>,
+
+    /// Secondary color of the line that will be cut last [default: blue]
+    #[clap(short, long)]
+    secondary_color: Option<Color>,
+
+    /// Take colors of every operation from the palette of laser software: lightburn, xtool or
+    /// glowforge. --primary-color and --secondary-color override it
+    #[arg(long)]
+    palette: Option<Palette>,
+
+    /// Unit of the SVG document size and printed dimensions: mm or in [default: mm, or the
+    /// unit of the output profile]
+    #[arg(short, long)]
+    units: Option<Unit>,
+
+    /// Write the SVG for laser software: generic, lightburn, epilog, trotec, xtool or
+    /// glowforge. Sets stroke width, document size and layers [default: generic]
+    #[arg(long)]
+    output_profile: Option<LaserSoftware>,
+
+    /// Project file (TOML or JSON) to generate from. Other arguments override its values
+    #[arg(long)]
+    project: Option<PathBuf>,
+
+    /// Save the configuration used for generation to a project file (TOML or JSON)
+    #[arg(long)]
+    save_project: Option<PathBuf>,
+
+    /// Place side wings, covers and side panels edge to edge and cut shared edges only once
+    #[arg(long)]
+    common_line: bool,
+
+    /// Engrave the label of every part on it, e.g. "Side panel 2"
+    #[arg(long)]
+    engrave_labels: bool,
+
+    /// Leave this many micro-tabs in the outline of every part, so parts stay in the sheet
+    #[arg(long)]
+    micro_tabs: Option<usize>,
+
+    /// Width of one micro-tab, e.g. 0.6, 0.6mm or 1/32in [default: 0.6mm]
+    #[arg(long)]
+    micro_tab_width: Option<Length>,
+
+    /// Parts getting micro-tabs, comma separated: side-wing, cover, side-panel [default: all]
+    #[arg(long, value_delimiter = ',')]
+    micro_tab_parts: Option<Vec<PartKind>>,
+
+    /// Print estimated laser time, using default machine speeds unless --machine is given
+    #[arg(long)]
+    estimate: bool,
+
+    /// Machine profile (TOML) with cut_speed, cut_speeds, travel_speed, pierce_delay and
+    /// price_per_minute for estimating laser time and cost
+    #[arg(long)]
+    machine: Option<PathBuf>,
 
-    /// Primary color of the line that will be cut first
-    #[clap(short, long, default_value = "blue")]
-    secondary_color: String,
+    /// Price of one stock sheet, added to the estimated cost
+    #[arg(long, requires_all = ["sheet_width", "sheet_height"])]
+    sheet_price: Option<f32>,
+
+    /// Width of the stock sheet, e.g. 600, 600mm or 24in
+    #[arg(long)]
+    sheet_width: Option<Length>,
+
+    /// Height of the stock sheet
+    #[arg(long)]
+    sheet_height: Option<Length>,
 }
 
 /// Generate SVG for the container rack
 pub fn svg(args: &RackGenerationArgs) {
+    let project = match effective_project(args) {
+        Ok(project) => project,
+        Err(error) => {
+            println!("{}", error);
+            //exit from process
+            std::process::exit(1);
+        }
+    };
     println!(
-        "So you want to generate organizer with {} rows and {} columns, using {}mm thick material.",
-        args.rows, args.columns, args.material_thickness
+        "So you want to generate organizer with {} rows and {} columns, using {} thick material.",
+        project.rows, project.columns, project.material_thickness
     );
-    let supported_containers = supported_containers();
 
-    let container = match supported_containers
-        .iter()
-        .find(|c| c.key() == args.container)
+    let (container, generated_doc) = match project
+        .resolve_container()
+        .and_then(|container| {
+            project
+                .generate_for(&container)
+                .map(|generated| (container, generated))
+        })
     {
-        Some(container) => container,
-        None => {
-            println!("No supported containers found.");
-            //exit from process
+        Ok(generated) => generated,
+        Err(error) => {
+            println!("{}", error);
             std::process::exit(1);
         }
     };
+    // Only projects which generate are saved
+    if let Some(path) = &args.save_project {
+        match project.save(path) {
+            Ok(()) => println!("Project saved to {}", path.to_string_lossy()),
+            Err(error) => {
+                println!("{}", error);
+                std::process::exit(1);
+            }
+        }
+    }
 
-    let generated_doc = generate_svg(
-        args.rows,
-        args.columns,
-        args.material_thickness,
-        &container,
-        &args.primary_color,
-        &args.secondary_color,
-    );
     let svg = &generated_doc.document;
-    let filename = match args.output_filename.clone() {
-        Some(name) => name,
-        None => format!(
-            "organizer_{}_rows_{}_columns_{}mm_thick_{}",
-            args.rows,
-            args.columns,
-            args.material_thickness,
-            container.key()
-        ),
-    };
-    let filename_with_extension = format!("{}.svg", filename);
+    let filename_with_extension = format!("{}.svg", project.filename(&container));
     svg::save(&filename_with_extension, svg).unwrap();
-    println!("Container size: {:.1}mm (W) x {:.1}mm (H) x {:.1}mm (D)", generated_doc.assembled_dimensions.width, generated_doc.assembled_dimensions.height, generated_doc.assembled_dimensions.depth);
+    println!(
+        "{}",
+        format_dimensions(&generated_doc.assembled_dimensions, project.output.document_unit())
+    );
     println!("Saved to {}", &filename_with_extension);
+    println!("{}", format_stats(&generated_doc.stats, project.output.document_unit()));
+
+    if args.estimate || args.machine.is_some() {
+        print_estimate(args, &project, &container, &generated_doc);
+    }
+}
+
+/// Print laser time and cost estimate
+fn print_estimate(
+    args: &RackGenerationArgs,
+    project: &RackProject,
+    container: &Container,
+    generated: &GeneratedSvg,
+) {
+    let machine = match &args.machine {
+        Some(path) => match MachineProfile::load(path) {
+            Ok(machine) => machine,
+            Err(error) => {
+                println!("{}", error);
+                std::process::exit(1);
+            }
+        },
+        None => MachineProfile::default(),
+    };
+    let estimate = estimate_job(generated, &machine);
+    println!("{}", format_estimate(&estimate));
+
+    let sheets = match (args.sheet_width, args.sheet_height) {
+        (Some(width), Some(height)) => {
+            let sheet = SheetDimensions {
+                width: width.to_mm(),
+                height: height.to_mm(),
+            };
+            let parts = part_sizes(
+                project.rows,
+                project.columns,
+                project.material_thickness.to_mm(),
+                &container.dimensions,
+            );
+            match pack(&parts, sheet) {
+                Ok(layout) => Some(layout.sheet_count),
+                Err(error) => {
+                    println!("{}", error);
+                    std::process::exit(1);
+                }
+            }
+        }
+        _ => None,
+    };
+
+    match (sheets, args.sheet_price) {
+        (Some(sheets), Some(price)) => println!(
+            "Estimated cost: {:.2} (laser {:.2} + {} sheets x {:.2})",
+            estimate.total_cost(sheets, price),
+            estimate.laser_cost(),
+            sheets,
+            price
+        ),
+        (Some(sheets), None) => {
+            println!("Sheets needed: {}", sheets);
+            println!("Estimated laser cost: {:.2}", estimate.laser_cost());
+        }
+        _ => println!("Estimated laser cost: {:.2}", estimate.laser_cost()),
+    }
+}
+
+fn format_estimate(estimate: &JobEstimate) -> String {
+    format!(
+        "Estimated laser time: {} (cut {}, travel {}, {} pierces {})",
+        format_duration(estimate.total_time()),
+        format_duration(estimate.cut_time),
+        format_duration(estimate.travel_time),
+        estimate.pierces,
+        format_duration(estimate.pierce_time)
+    )
+}
+
+/// Format seconds like `1h 2m 3s`
+fn format_duration(seconds: f32) -> String {
+    let seconds = seconds.round() as u64;
+    let (hours, minutes, seconds) = (seconds / 3600, seconds % 3600 / 60, seconds % 60);
+
+    if hours > 0 {
+        format!("{}h {}m {}s", hours, minutes, seconds)
+    } else if minutes > 0 {
+        format!("{}m {}s", minutes, seconds)
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
+/// Project file overridden by given arguments, or project built from arguments only
+fn effective_project(args: &RackGenerationArgs) -> Result<RackProject, ProjectError> {
+    let container = match &args.container {
+        Some(query) => Some(ProjectContainer::Key(
+            find_container(query)
+                .map_err(ProjectError::Container)?
+                .key(),
+        )),
+        None => None,
+    };
+
+    let mut project = match &args.project {
+        Some(path) => RackProject::load(path)?,
+        // clap requires these arguments when there is no project file
+        None => RackProject::new(
+            args.rows.unwrap_or_default(),
+            args.columns.unwrap_or_default(),
+            args.material_thickness.unwrap_or(Length::millimeters(0.0)),
+            container.clone().unwrap_or(ProjectContainer::Key(String::new())),
+        ),
+    };
+
+    if let Some(rows) = args.rows {
+        project.rows = rows;
+    }
+    if let Some(columns) = args.columns {
+        project.columns = columns;
+    }
+    if let Some(material_thickness) = args.material_thickness {
+        project.material_thickness = material_thickness;
+    }
+    if let Some(container) = container {
+        project.container = container;
+    }
+    if let Some(filename) = &args.output_filename {
+        project.output.filename = Some(filename.clone());
+    }
+    if let Some(palette) = args.palette {
+        project.colors = ProjectColors::from_palette(palette);
+    }
+    if let Some(color) = &args.primary_color {
+        project.colors.primary = *color;
+    }
+    if let Some(color) = &args.secondary_color {
+        project.colors.secondary = *color;
+    }
+    if let Some(software) = args.output_profile {
+        project.output.profile = software;
+        project.output.unit = None;
+    }
+    if let Some(unit) = args.units {
+        project.output.unit = Some(unit);
+    }
+    if args.common_line {
+        project.cutting.common_line = true;
+    }
+    if args.engrave_labels {
+        project.output.engrave_labels = true;
+    }
+    if args.micro_tabs.is_some() || args.micro_tab_width.is_some() || args.micro_tab_parts.is_some()
+    {
+        let micro_tabs = project.cutting.micro_tabs.get_or_insert_with(MicroTabs::default);
+        if let Some(count) = args.micro_tabs {
+            micro_tabs.count = count;
+        }
+        if let Some(width) = args.micro_tab_width {
+            micro_tabs.width = width.to_mm();
+        }
+        if let Some(parts) = &args.micro_tab_parts {
+            micro_tabs.parts = parts.clone();
+        }
+    }
+
+    Ok(project)
+}
+
+/// Format assembled dimensions in given unit, each with the precision of `format_length`
+fn format_dimensions(dimensions: &AssembledDimensions, unit: Unit) -> String {
+    let dimensions = dimensions.to_unit(unit);
+
+    format!(
+        "Container size: {} (W) x {} (H) x {} (D)",
+        format_length(dimensions.width, unit),
+        format_length(dimensions.height, unit),
+        format_length(dimensions.depth, unit)
+    )
+}
+
+/// Format layout size, material use, part counts and cut lengths
+fn format_stats(stats: &LayoutStats, unit: Unit) -> String {
+    let parts = stats
+        .part_counts
+        .iter()
+        .map(|(kind, count)| format!("{} x {}", count, kind))
+        .collect::<Vec<String>>()
+        .join(", ");
+    let cut_lengths = stats
+        .cut_length_by_color
+        .iter()
+        .map(|(color, length)| format!("{} {}", color, format_length(unit.from_mm(*length), unit)))
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    [
+        format!(
+            "Layout: {} x {}, parts cover {:.1}% of it",
+            format_length(unit.from_mm(stats.bounding_box.width), unit),
+            format_length(unit.from_mm(stats.bounding_box.height), unit),
+            stats.utilisation * 100.0
+        ),
+        format!("Parts: {}", parts),
+        format!("Cut length: {}", cut_lengths),
+    ]
+    .join("\n")
 }
 
 #[cfg(test)]
 mod tests {
-    use container_rack_lib::rack::AssembledDimensions;
+    use super::{effective_project, RackGenerationArgs};
+    use clap::Parser;
+    use container_rack_lib::color::Color;
+    use container_rack_lib::catalogue::{builtin_containers, find_container_in};
+    use container_rack_lib::project::{ProjectContainer, RackProject};
+    use container_rack_lib::rack::output::LaserSoftware;
+    use container_rack_lib::rack::tabs::MicroTabs;
+    use container_rack_lib::rack::{AssembledDimensions, PartKind};
+    use container_rack_lib::units::{Length, Unit};
 
     // Helper function to format dimensions like the CLI does
     fn format_dimensions(dims: &AssembledDimensions) -> String {
-        format!(
-            "Container size: {:.1}mm (W) x {:.1}mm (H) x {:.1}mm (D)",
-            dims.width, dims.height, dims.depth
-        )
+        super::format_dimensions(dims, Unit::Millimeter)
     }
 
     // Unit tests for CLI output formatting
@@ -222,7 +518,7 @@ mod tests {
         // 5. println!("Saved to {}") <- save confirmation
 
         // This test documents the expected order
-        let expected_order = vec![
+        let expected_order = [
             "Container size output",
             "Save confirmation output",
         ];
@@ -252,5 +548,190 @@ mod tests {
         // Verify they match
         assert_eq!(helper_output, cli_format);
     }
-}
 
+    #[test]
+    fn test_format_in_inches() {
+        let dims = AssembledDimensions {
+            width: 254.0,
+            height: 127.0,
+            depth: 210.0,
+        };
+
+        let output = super::format_dimensions(&dims, Unit::Inch);
+
+        assert_eq!(
+            output,
+            "Container size: 10.00in (W) x 5.00in (H) x 8.27in (D)"
+        );
+    }
+
+    #[test]
+    fn test_arguments_override_project_file() {
+        let path = std::env::temp_dir().join(format!("container-rack-cli-{}.toml", std::process::id()));
+        let mut project = RackProject::new(
+            8,
+            3,
+            Length::millimeters(4.0),
+            ProjectContainer::Key("smartstore-classic_1".to_string()),
+        );
+        project.colors.primary = Color::Named("red");
+        project.save(&path).unwrap();
+
+        let args = RackGenerationArgs::parse_from([
+            "generate",
+            "--project",
+            path.to_str().unwrap(),
+            "--rows",
+            "2",
+            "--container",
+            "ikea-samla_11l",
+            "--units",
+            "in",
+        ]);
+        let effective = effective_project(&args).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(effective.rows, 2);
+        assert_eq!(effective.columns, 3);
+        assert_eq!(effective.container, ProjectContainer::Key("ikea-samla_11l".to_string()));
+        assert_eq!(effective.colors.primary, Color::Named("red"));
+        assert_eq!(effective.colors.secondary, Color::Named("blue"));
+        assert_eq!(effective.output.unit, Some(Unit::Inch));
+    }
+
+    #[test]
+    fn test_output_profile_argument() {
+        let with = |extra: &[&str]| {
+            let mut arguments = vec![
+                "generate",
+                "--rows",
+                "1",
+                "--columns",
+                "1",
+                "--material-thickness",
+                "4",
+                "--container",
+                "smartstore-classic_1",
+            ];
+            arguments.extend_from_slice(extra);
+            effective_project(&RackGenerationArgs::parse_from(arguments)).unwrap()
+        };
+
+        let epilog = with(&["--output-profile", "epilog"]);
+        assert_eq!(epilog.output.profile, LaserSoftware::Epilog);
+        assert_eq!(epilog.output.unit, None);
+        assert_eq!(epilog.output.document_unit(), Unit::Inch);
+
+        let explicit_unit = with(&["--output-profile", "epilog", "--units", "mm"]);
+        assert_eq!(explicit_unit.output.unit, Some(Unit::Millimeter));
+    }
+
+    #[test]
+    fn test_format_stats() {
+        let project = RackProject::new(
+            1,
+            1,
+            Length::millimeters(3.0),
+            ProjectContainer::Inline(
+                find_container_in(builtin_containers(), "smartstore-classic_1").unwrap(),
+            ),
+        );
+        let stats = project.generate().unwrap().stats;
+
+        let output = super::format_stats(&stats, Unit::Millimeter);
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert!(lines[0].starts_with("Layout: 458.0mm x 423.0mm, parts cover "));
+        assert_eq!(lines[1], "Parts: 2 x side wing, 2 x cover, 2 x side panel");
+        assert!(lines[2].starts_with("Cut length: black "));
+        assert!(lines[2].contains(", blue "));
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(super::format_duration(42.4), "42s");
+        assert_eq!(super::format_duration(432.0), "7m 12s");
+        assert_eq!(super::format_duration(3723.0), "1h 2m 3s");
+    }
+
+    #[test]
+    fn test_sheet_price_requires_sheet_size() {
+        let args = ["generate", "-r", "2", "-c", "3", "-m", "4", "--container", "smartstore-classic_1"];
+
+        assert!(RackGenerationArgs::try_parse_from(args.iter().chain(&["--sheet-price", "5"])).is_err());
+        assert!(RackGenerationArgs::try_parse_from(args.iter().chain(&[
+            "--sheet-price",
+            "5",
+            "--sheet-width",
+            "600",
+            "--sheet-height",
+            "400"
+        ]))
+        .is_ok());
+    }
+
+    #[test]
+    fn test_color_arguments() {
+        let base = ["generate", "-r", "1", "-c", "1", "-m", "4", "--container", "smartstore-classic_1"];
+        let with = |extra: &[&'static str]| {
+            RackGenerationArgs::try_parse_from(base.iter().chain(extra.iter()).copied())
+        };
+
+        assert!(with(&["--primary-color", "blakc"]).is_err());
+
+        let args = with(&["--palette", "lightburn", "--secondary-color", "#F00"]).unwrap();
+        let effective = effective_project(&args).unwrap();
+        assert_eq!(effective.colors.primary, Color::Rgb(0, 0, 0));
+        assert_eq!(effective.colors.secondary, Color::Rgb(255, 0, 0));
+        assert_eq!(effective.colors.score, Color::Rgb(255, 0, 0));
+    }
+
+    #[test]
+    fn test_micro_tab_arguments() {
+        let args = RackGenerationArgs::parse_from([
+            "generate",
+            "-r",
+            "2",
+            "-c",
+            "3",
+            "-m",
+            "4",
+            "--container",
+            "smartstore-classic_1",
+            "--micro-tabs",
+            "3",
+            "--micro-tab-parts",
+            "side-wing,side-panel",
+        ]);
+        let effective = effective_project(&args).unwrap();
+
+        assert_eq!(
+            effective.cutting.micro_tabs,
+            Some(MicroTabs {
+                count: 3,
+                parts: vec![PartKind::SideWing, PartKind::SidePanel],
+                ..MicroTabs::default()
+            })
+        );
+    }
+
+    #[test]
+    fn test_arguments_without_project_file() {
+        assert!(RackGenerationArgs::try_parse_from(["generate", "--rows", "2"]).is_err());
+
+        let args = RackGenerationArgs::parse_from([
+            "generate", "-r", "2", "-c", "3", "-m", "1/4in", "--container", "smartstore-classic_1",
+        ]);
+        let effective = effective_project(&args).unwrap();
+
+        assert_eq!(
+            effective,
+            RackProject::new(
+                2,
+                3,
+                Length::inches(0.25),
+                ProjectContainer::Key("smartstore-classic_1".to_string())
+            )
+        );
+    }
+}
@@ -0,0 +1,84 @@
+use std::path::PathBuf;
+
+use clap::Args;
+use container_rack_lib::metadata::{inspect_file, DesignMetadata};
+
+#[derive(Args, Debug)]
+pub struct InspectArgs {
+    /// SVG file generated by this tool
+    file: PathBuf,
+
+    /// Save the parameters to a project file (TOML or JSON), e.g. to regenerate the design with
+    /// `generate --project`
+    #[arg(long)]
+    save_project: Option<PathBuf>,
+}
+
+/// Print parameters used to generate the SVG
+pub fn run(args: &InspectArgs) {
+    let metadata = match inspect_file(&args.file) {
+        Ok(metadata) => metadata,
+        Err(error) => {
+            println!("{}", error);
+            std::process::exit(1);
+        }
+    };
+
+    match metadata.project.to_toml() {
+        Ok(project) => println!("{}", describe(&metadata, &project)),
+        Err(error) => {
+            println!("{}", error);
+            std::process::exit(1);
+        }
+    }
+
+    if let Some(path) = &args.save_project {
+        match metadata.project.save(path) {
+            Ok(()) => println!("Project saved to {}", path.to_string_lossy()),
+            Err(error) => {
+                println!("{}", error);
+                std::process::exit(1);
+            }
+        }
+    }
+}
+
+fn describe(metadata: &DesignMetadata, project: &str) -> String {
+    let mut lines = vec![format!(
+        "Generated with {} {}",
+        metadata.generator, metadata.version
+    )];
+    if metadata.is_outdated() {
+        lines.push(format!(
+            "This version is {}, save the project and generate it again to upgrade the design",
+            env!("CARGO_PKG_VERSION")
+        ));
+    }
+    lines.push(String::new());
+    lines.push(project.trim_end().to_string());
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use container_rack_lib::project::{ProjectContainer, RackProject};
+    use container_rack_lib::units::Length;
+
+    #[test]
+    fn test_describe_outdated_design() {
+        let mut metadata = DesignMetadata::new(RackProject::new(
+            1,
+            2,
+            Length::millimeters(4.0),
+            ProjectContainer::Key("smartstore-classic_1".to_string()),
+        ));
+        metadata.version = "0.1.0".to_string();
+
+        let description = describe(&metadata, &metadata.project.to_toml().unwrap());
+
+        assert!(description.starts_with("Generated with container-rack-lib 0.1.0\nThis version is"));
+        assert!(description.contains("\ncontainer = \"smartstore-classic_1\"\n"));
+    }
+}
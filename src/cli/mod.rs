@@ -1,8 +1,22 @@
 use clap::{Parser, Subcommand};
+use container_rack_lib::units::Unit;
 
 pub mod generate;
 use generate::RackGenerationArgs;
+pub mod add_container;
+pub mod batch;
+use batch::BatchArgs;
 pub mod containers;
+use containers::ContainersArgs;
+pub mod cross_section;
+pub mod inspect;
+use inspect::InspectArgs;
+pub mod optimise;
+use optimise::OptimiseArgs;
+pub mod plan;
+use plan::PlanArgs;
+pub mod validate;
+use validate::ValidateArgs;
 
 #[derive(Parser, Debug)]
 #[structopt(name = "Container rack")]
@@ -16,9 +30,21 @@ struct CommandLineCommands {
 #[derive(Subcommand, Debug)]
 enum Commands {
     /// Generate SVG
-    Generate(RackGenerationArgs),
+    Generate(Box<RackGenerationArgs>),
     /// List supported containers
-    Containers,
+    Containers(ContainersArgs),
+    /// Show parameters embedded to a generated SVG
+    Inspect(InspectArgs),
+    /// Generate every rack listed in a manifest
+    Batch(BatchArgs),
+    /// List row and column combinations that fit a given space
+    Plan(PlanArgs),
+    /// Find rack configurations that use stock sheets most efficiently
+    Optimise(OptimiseArgs),
+    /// Check that parts of a generated rack can be cut and fit together: closed shapes, no
+    /// overlaps, enough material around holes, no features narrower than the kerf allows and
+    /// every tab meeting its slot
+    Validate(ValidateArgs),
 }
 
 pub fn run() {
@@ -28,8 +54,31 @@ pub fn run() {
         Commands::Generate(args) => {
             generate::svg(args);
         }
-        Commands::Containers => {
-            containers::print_containers();
+        Commands::Containers(args) => {
+            containers::run(args);
         }
+        Commands::Inspect(args) => {
+            inspect::run(args);
+        }
+        Commands::Batch(args) => {
+            batch::run(args);
+        }
+        Commands::Plan(args) => {
+            plan::run(args);
+        }
+        Commands::Optimise(args) => {
+            optimise::run(args);
+        }
+        Commands::Validate(args) => {
+            validate::run(args);
+        }
+    }
+}
+
+/// Format length given in unit, inches get one more decimal
+fn format_length(value: f32, unit: Unit) -> String {
+    match unit {
+        Unit::Millimeter => format!("{:.1}{}", value, unit),
+        Unit::Inch => format!("{:.2}{}", value, unit),
     }
 }
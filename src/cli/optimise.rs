@@ -0,0 +1,118 @@
+use clap::Args;
+use comfy_table::{Cell, Table};
+use container_rack_lib::catalogue::find_container;
+use container_rack_lib::rack::SheetDimensions;
+use container_rack_lib::sheets::{optimise, SheetPlan};
+use container_rack_lib::units::Length;
+
+#[derive(Args, Debug)]
+pub struct OptimiseArgs {
+    /// Width of the stock sheet, e.g. 600, 600mm or 24in. Defaults to mm
+    #[arg(long)]
+    sheet_width: Length,
+
+    /// Height of the stock sheet
+    #[arg(long)]
+    sheet_height: Length,
+
+    /// Key of container, e.g. smartstore-classic_1
+    #[arg(long)]
+    container: String,
+
+    /// Thickness of the plywood or other material, e.g. 4, 4mm or 1/4in. Defaults to mm
+    #[arg(short, long)]
+    material_thickness: Length,
+
+    /// Most rows to evaluate
+    #[arg(long, default_value_t = 8)]
+    max_rows: usize,
+
+    /// Most columns to evaluate
+    #[arg(long, default_value_t = 4)]
+    max_columns: usize,
+
+    /// Show only this many best configurations
+    #[arg(short, long)]
+    limit: Option<usize>,
+}
+
+/// Print rack configurations ordered by how well they use the stock sheets
+pub fn run(args: &OptimiseArgs) {
+    let container = match find_container(&args.container) {
+        Ok(container) => container,
+        Err(error) => {
+            println!("{}", error);
+            std::process::exit(1);
+        }
+    };
+    let sheet = SheetDimensions {
+        width: args.sheet_width.to_mm(),
+        height: args.sheet_height.to_mm(),
+    };
+
+    let mut plans = optimise(
+        &container.dimensions,
+        args.material_thickness.to_mm(),
+        sheet,
+        args.max_rows,
+        args.max_columns,
+    );
+    if plans.is_empty() {
+        println!(
+            "Parts of a rack for {} {} don't fit on a {} x {} sheet",
+            container.vendor, container.model, args.sheet_width, args.sheet_height
+        );
+        std::process::exit(1);
+    }
+    if let Some(limit) = args.limit {
+        plans.truncate(limit);
+    }
+
+    println!("{}", plan_table(&plans));
+}
+
+fn plan_table(plans: &[SheetPlan]) -> Table {
+    let mut table = Table::new();
+    table.set_header(vec![
+        Cell::new("Rows"),
+        Cell::new("Columns"),
+        Cell::new("Boxes"),
+        Cell::new("Sheets"),
+        Cell::new("Waste"),
+        Cell::new("Boxes per sheet"),
+    ]);
+
+    for plan in plans {
+        table.add_row(vec![
+            Cell::new(plan.rows),
+            Cell::new(plan.columns),
+            Cell::new(plan.boxes()),
+            Cell::new(plan.sheets_required()),
+            Cell::new(format!("{:.1}%", plan.waste_percentage())),
+            Cell::new(format!("{:.1}", plan.boxes_per_sheet())),
+        ]);
+    }
+
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use container_rack_lib::catalogue::{builtin_containers, find_container_in};
+
+    #[test]
+    fn test_plan_table() {
+        let container = find_container_in(builtin_containers(), "classic1").unwrap();
+        let sheet = SheetDimensions {
+            width: 600.0,
+            height: 450.0,
+        };
+        let plans = optimise(&container.dimensions, 4.0, sheet, 2, 2);
+
+        let table = plan_table(&plans).to_string();
+
+        assert_eq!(table.matches('%').count(), 4);
+        assert!(table.contains("Boxes per sheet"));
+    }
+}
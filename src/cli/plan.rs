@@ -0,0 +1,137 @@
+use clap::Args;
+use comfy_table::{Cell, Table};
+use container_rack_lib::catalogue::find_container;
+use container_rack_lib::planner::{plan, AvailableSpace, RackPlan};
+use container_rack_lib::units::{Length, Unit};
+
+use crate::cli::format_length;
+
+#[derive(Args, Debug)]
+pub struct PlanArgs {
+    /// Available width, e.g. 800, 800mm or 31 1/2in. Defaults to mm
+    #[arg(long)]
+    width: Length,
+
+    /// Available height
+    #[arg(long)]
+    height: Length,
+
+    /// Available depth
+    #[arg(long)]
+    depth: Length,
+
+    /// Key of container, e.g. smartstore-classic_1
+    #[arg(long)]
+    container: String,
+
+    /// Thickness of the plywood or other material, e.g. 4, 4mm or 1/4in. Defaults to mm
+    #[arg(short, long)]
+    material_thickness: Length,
+
+    /// Also list plans where the rack is turned a quarter around
+    #[arg(long)]
+    rotated: bool,
+
+    /// Show only this many best plans
+    #[arg(short, long)]
+    limit: Option<usize>,
+
+    /// Unit of printed dimensions: mm or in [default: mm]
+    #[arg(short, long, default_value_t = Unit::Millimeter)]
+    units: Unit,
+}
+
+/// Print rack plans that fit the given space
+pub fn run(args: &PlanArgs) {
+    let container = match find_container(&args.container) {
+        Ok(container) => container,
+        Err(error) => {
+            println!("{}", error);
+            std::process::exit(1);
+        }
+    };
+    let space = AvailableSpace {
+        width: args.width.to_mm(),
+        height: args.height.to_mm(),
+        depth: args.depth.to_mm(),
+    };
+
+    let mut plans = plan(
+        &space,
+        &container.dimensions,
+        args.material_thickness.to_mm(),
+        args.rotated,
+    );
+    if plans.is_empty() {
+        println!(
+            "Not even one {} {} fits in the space",
+            container.vendor, container.model
+        );
+        std::process::exit(1);
+    }
+    if let Some(limit) = args.limit {
+        plans.truncate(limit);
+    }
+
+    println!("{}", plan_table(&plans, args.units));
+}
+
+fn plan_table(plans: &[RackPlan], unit: Unit) -> Table {
+    let mut table = Table::new();
+    table.set_header(vec![
+        Cell::new("Rows"),
+        Cell::new("Columns"),
+        Cell::new("Containers"),
+        Cell::new("Orientation"),
+        Cell::new("Assembled (W x H x D)"),
+        Cell::new("Leftover (W x H x D)"),
+    ]);
+
+    for plan in plans {
+        let assembled = plan.assembled_dimensions.to_unit(unit);
+        let leftover = &plan.leftover;
+        table.add_row(vec![
+            Cell::new(plan.rows),
+            Cell::new(plan.columns),
+            Cell::new(plan.container_count()),
+            Cell::new(plan.orientation),
+            Cell::new(format!(
+                "{} x {} x {}",
+                format_length(assembled.width, unit),
+                format_length(assembled.height, unit),
+                format_length(assembled.depth, unit)
+            )),
+            Cell::new(format!(
+                "{} x {} x {}",
+                format_length(unit.from_mm(leftover.width), unit),
+                format_length(unit.from_mm(leftover.height), unit),
+                format_length(unit.from_mm(leftover.depth), unit)
+            )),
+        ]);
+    }
+
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use container_rack_lib::catalogue::{builtin_containers, find_container_in};
+
+    #[test]
+    fn test_plan_table() {
+        let container = find_container_in(builtin_containers(), "classic1").unwrap();
+        let space = AvailableSpace {
+            width: 800.0,
+            height: 250.0,
+            depth: 600.0,
+        };
+        let plans = plan(&space, &container.dimensions, 4.0, false);
+
+        let table = plan_table(&plans[..1], Unit::Millimeter).to_string();
+
+        assert!(table.contains("716.0mm x 232.0mm x 210.0mm"));
+        assert!(table.contains("84.0mm x 18.0mm x 390.0mm"));
+        assert!(table.contains("front"));
+    }
+}
@@ -0,0 +1,144 @@
+use std::path::PathBuf;
+
+use clap::Args;
+use container_rack_lib::metadata::inspect_file;
+use container_rack_lib::project::RackProject;
+use container_rack_lib::rack::assembly::Assembly;
+use container_rack_lib::units::Length;
+use container_rack_lib::validation::{validate, ValidationOptions};
+
+#[derive(Args, Debug)]
+pub struct ValidateArgs {
+    /// SVG file generated by this tool, the rack is generated again from its parameters
+    #[arg(required_unless_present = "project", conflicts_with = "project")]
+    file: Option<PathBuf>,
+
+    /// Project file (TOML or JSON) to validate instead of an SVG
+    #[arg(long)]
+    project: Option<PathBuf>,
+
+    /// Width of the material burned away by the laser, e.g. 0.2, 0.2mm or 0.008in
+    /// [default: 0.2mm]
+    #[arg(long)]
+    kerf: Option<Length>,
+
+    /// Slots and pieces of material must be at least this many kerfs wide [default: 3]
+    #[arg(long)]
+    kerf_multiple: Option<f32>,
+
+    /// Narrowest material allowed around holes [default: 2mm]
+    #[arg(long)]
+    minimum_web: Option<Length>,
+}
+
+/// Check the parts of a rack and how they fit together, and print problems of every part
+pub fn run(args: &ValidateArgs) {
+    let project = match load_project(args) {
+        Ok(project) => project,
+        Err(error) => {
+            println!("{}", error);
+            std::process::exit(1);
+        }
+    };
+    let (container, generated) = match project
+        .resolve_container()
+        .and_then(|container| {
+            project
+                .generate_for(&container)
+                .map(|generated| (container, generated))
+        })
+    {
+        Ok(generated) => generated,
+        Err(error) => {
+            println!("{}", error);
+            std::process::exit(1);
+        }
+    };
+
+    let mut options = validation_options(args);
+    options.micro_tab_width = project
+        .cutting
+        .micro_tabs
+        .as_ref()
+        .map(|micro_tabs| micro_tabs.width);
+    let report = validate(&generated.parts, &options);
+    let assembly = Assembly::new(
+        project.rows,
+        project.columns,
+        project.material_thickness.to_mm(),
+        &container.dimensions,
+        &generated.parts,
+    );
+    let interferences = assembly.interferences();
+    let loose_tabs = assembly.loose_tabs();
+
+    if report.is_valid() && interferences.is_empty() && loose_tabs.is_empty() {
+        println!("{}", report);
+        return;
+    }
+    if !report.is_valid() {
+        println!("{}", report);
+    }
+    for interference in interferences {
+        println!("{}", interference);
+    }
+    for loose_tab in loose_tabs {
+        println!("{}", loose_tab);
+    }
+    std::process::exit(1);
+}
+
+fn load_project(args: &ValidateArgs) -> Result<RackProject, String> {
+    match (&args.file, &args.project) {
+        (Some(file), _) => inspect_file(file)
+            .map(|metadata| metadata.project)
+            .map_err(|error| error.to_string()),
+        (None, Some(project)) => RackProject::load(project).map_err(|error| error.to_string()),
+        // clap requires one of them
+        (None, None) => Err("Give an SVG file or --project".to_string()),
+    }
+}
+
+fn validation_options(args: &ValidateArgs) -> ValidationOptions {
+    let mut options = ValidationOptions::default();
+    if let Some(kerf) = args.kerf {
+        options.kerf = kerf.to_mm();
+    }
+    if let Some(kerf_multiple) = args.kerf_multiple {
+        options.kerf_multiple = kerf_multiple;
+    }
+    if let Some(minimum_web) = args.minimum_web {
+        options.minimum_web = minimum_web.to_mm();
+    }
+    options
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[derive(Parser)]
+    struct Command {
+        #[command(flatten)]
+        args: ValidateArgs,
+    }
+
+    #[test]
+    fn test_validation_options() {
+        let command = Command::parse_from([
+            "validate",
+            "rack.svg",
+            "--kerf",
+            "0.1in",
+            "--minimum-web",
+            "3",
+        ]);
+
+        let options = validation_options(&command.args);
+
+        assert!((options.kerf - 2.54).abs() < 1e-4);
+        assert_eq!(options.kerf_multiple, 3.0);
+        assert_eq!(options.minimum_web, 3.0);
+    }
+}
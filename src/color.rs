@@ -0,0 +1,452 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Color of lines in the SVG.
+///
+/// Named CSS colors are kept by their name, other colors are written as `#RRGGBB` so laser
+/// software sees the exact same value for the same color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Named(&'static str),
+    Rgb(u8, u8, u8),
+}
+
+impl Color {
+    /// Red, green and blue components
+    pub fn rgb(&self) -> (u8, u8, u8) {
+        match self {
+            Color::Named(name) => NAMED_COLORS
+                .iter()
+                .find(|(named, _)| named == name)
+                .map(|(_, hex)| parse_hex(&hex[1..]).expect("named colors are valid hex"))
+                .expect("named color is in the table"),
+            Color::Rgb(red, green, blue) => (*red, *green, *blue),
+        }
+    }
+
+    /// Color as `#RRGGBB`
+    pub fn to_hex(&self) -> String {
+        let (red, green, blue) = self.rgb();
+        format!("#{:02X}{:02X}{:02X}", red, green, blue)
+    }
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Color::Named(name) => write!(f, "{}", name),
+            Color::Rgb(..) => write!(f, "{}", self.to_hex()),
+        }
+    }
+}
+
+impl FromStr for Color {
+    type Err = String;
+
+    /// Parse named CSS color, `#rgb`, `#rrggbb`, `rgb(r, g, b)` or palette color like
+    /// `lightburn:02`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let trimmed = s.trim().to_lowercase();
+
+        if let Some(&(name, _)) = NAMED_COLORS.iter().find(|(name, _)| *name == trimmed) {
+            return Ok(Color::Named(name));
+        }
+        if let Some(hex) = trimmed.strip_prefix('#') {
+            let (red, green, blue) = parse_hex(hex).ok_or_else(|| {
+                format!("Invalid color '{}', hex colors are #rgb or #rrggbb", s.trim())
+            })?;
+            return Ok(Color::Rgb(red, green, blue));
+        }
+        if let Some(components) = trimmed
+            .strip_prefix("rgb(")
+            .and_then(|rest| rest.strip_suffix(')'))
+        {
+            let (red, green, blue) = parse_rgb_function(components).ok_or_else(|| {
+                format!(
+                    "Invalid color '{}', expected rgb(red, green, blue) with values from 0 to 255",
+                    s.trim()
+                )
+            })?;
+            return Ok(Color::Rgb(red, green, blue));
+        }
+        if let Some((palette, entry)) = trimmed.split_once(':') {
+            return palette.parse::<Palette>()?.color(entry);
+        }
+
+        Err(format!(
+            "Unknown color '{}', expected a CSS color name, #rgb, #rrggbb, rgb(r, g, b) or a palette color like lightburn:02",
+            s.trim()
+        ))
+    }
+}
+
+impl Serialize for Color {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Color {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// Parse color given by user and write it in the normalised form
+pub fn normalise_color(color: &str) -> Result<String, String> {
+    color.parse::<Color>().map(|color| color.to_string())
+}
+
+/// Whether two colors are the same color however they are written, e.g. `blue` and
+/// `#0000FF`. Values which aren't colors are the same only when they are equal.
+pub fn same_color(a: &str, b: &str) -> bool {
+    match (a.parse::<Color>(), b.parse::<Color>()) {
+        (Ok(a), Ok(b)) => a.rgb() == b.rgb(),
+        _ => a.trim() == b.trim(),
+    }
+}
+
+fn parse_hex(hex: &str) -> Option<(u8, u8, u8)> {
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    match hex.len() {
+        3 => {
+            let digit = |index: usize| u8::from_str_radix(&hex[index..=index], 16).ok();
+            Some((digit(0)? * 17, digit(1)? * 17, digit(2)? * 17))
+        }
+        6 => {
+            let byte = |index: usize| u8::from_str_radix(&hex[index..index + 2], 16).ok();
+            Some((byte(0)?, byte(2)?, byte(4)?))
+        }
+        _ => None,
+    }
+}
+
+/// Components separated by commas or spaces, as numbers from 0 to 255 or percentages
+fn parse_rgb_function(components: &str) -> Option<(u8, u8, u8)> {
+    let values: Vec<u8> = components
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|component| !component.is_empty())
+        .map(|component| match component.strip_suffix('%') {
+            Some(percentage) => {
+                let percentage: f32 = percentage.parse().ok()?;
+                (0.0..=100.0)
+                    .contains(&percentage)
+                    .then(|| (percentage * 2.55).round() as u8)
+            }
+            None => component.parse().ok(),
+        })
+        .collect::<Option<_>>()?;
+
+    match values[..] {
+        [red, green, blue] => Some((red, green, blue)),
+        _ => None,
+    }
+}
+
+/// Fixed color palettes of laser software. Each color of a palette is a separate layer with
+/// its own settings in the software.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Palette {
+    LightBurn,
+    XTool,
+    Glowforge,
+}
+
+impl Palette {
+    pub const ALL: [Palette; 3] = [Palette::LightBurn, Palette::XTool, Palette::Glowforge];
+
+    /// Colors of the palette in the order the software lists them
+    pub fn colors(&self) -> Vec<Color> {
+        let hexes: &[&str] = match self {
+            Palette::LightBurn => &LIGHTBURN_PALETTE,
+            Palette::XTool => &XTOOL_PALETTE,
+            Palette::Glowforge => &GLOWFORGE_PALETTE,
+        };
+
+        hexes
+            .iter()
+            .map(|hex| {
+                let (red, green, blue) = parse_hex(&hex[1..]).expect("palette colors are valid hex");
+                Color::Rgb(red, green, blue)
+            })
+            .collect()
+    }
+
+    /// Color by its number in the palette, e.g. `02` or `2`
+    pub fn color(&self, entry: &str) -> Result<Color, String> {
+        let colors = self.colors();
+
+        entry
+            .trim()
+            .parse::<usize>()
+            .ok()
+            .and_then(|index| colors.get(index).copied())
+            .ok_or_else(|| {
+                format!(
+                    "Unknown {} color '{}', expected a number from 0 to {}",
+                    self,
+                    entry.trim(),
+                    colors.len() - 1
+                )
+            })
+    }
+}
+
+impl fmt::Display for Palette {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Palette::LightBurn => write!(f, "lightburn"),
+            Palette::XTool => write!(f, "xtool"),
+            Palette::Glowforge => write!(f, "glowforge"),
+        }
+    }
+}
+
+impl FromStr for Palette {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().replace([' ', '-', '_'], "").as_str() {
+            "lightburn" => Ok(Palette::LightBurn),
+            "xtool" | "xtoolcreativespace" | "xcs" => Ok(Palette::XTool),
+            "glowforge" => Ok(Palette::Glowforge),
+            other => Err(format!(
+                "Unknown palette '{}', expected lightburn, xtool or glowforge",
+                other
+            )),
+        }
+    }
+}
+
+/// Layer colors 00 to 29 of LightBurn
+const LIGHTBURN_PALETTE: [&str; 30] = [
+    "#000000", "#0000FF", "#FF0000", "#00E000", "#D0D000", "#FF8000", "#00E0E0", "#FF00FF",
+    "#B4B4B4", "#0000A0", "#A00000", "#00A000", "#A0A000", "#C08000", "#00A0FF", "#A000A0",
+    "#808080", "#7D87B9", "#BB7784", "#4A6FE3", "#D33F6A", "#8CD78C", "#F0B98D", "#F6C4E1",
+    "#FA9ED4", "#500A78", "#B45A00", "#004754", "#86FA88", "#FFDB66",
+];
+
+/// Layer colors of xTool Creative Space
+const XTOOL_PALETTE: [&str; 12] = [
+    "#000000", "#0000FF", "#FF0000", "#00FF00", "#FFFF00", "#FF8000", "#00FFFF", "#FF00FF",
+    "#808080", "#800080", "#008080", "#804000",
+];
+
+/// Colors Glowforge tells apart as separate steps
+const GLOWFORGE_PALETTE: [&str; 8] = [
+    "#000000", "#0000FF", "#FF0000", "#00FF00", "#FF8000", "#FF00FF", "#00FFFF", "#808080",
+];
+
+/// Named colors of CSS
+const NAMED_COLORS: [(&str, &str); 148] = [
+    ("aliceblue", "#F0F8FF"),
+    ("antiquewhite", "#FAEBD7"),
+    ("aqua", "#00FFFF"),
+    ("aquamarine", "#7FFFD4"),
+    ("azure", "#F0FFFF"),
+    ("beige", "#F5F5DC"),
+    ("bisque", "#FFE4C4"),
+    ("black", "#000000"),
+    ("blanchedalmond", "#FFEBCD"),
+    ("blue", "#0000FF"),
+    ("blueviolet", "#8A2BE2"),
+    ("brown", "#A52A2A"),
+    ("burlywood", "#DEB887"),
+    ("cadetblue", "#5F9EA0"),
+    ("chartreuse", "#7FFF00"),
+    ("chocolate", "#D2691E"),
+    ("coral", "#FF7F50"),
+    ("cornflowerblue", "#6495ED"),
+    ("cornsilk", "#FFF8DC"),
+    ("crimson", "#DC143C"),
+    ("cyan", "#00FFFF"),
+    ("darkblue", "#00008B"),
+    ("darkcyan", "#008B8B"),
+    ("darkgoldenrod", "#B8860B"),
+    ("darkgray", "#A9A9A9"),
+    ("darkgreen", "#006400"),
+    ("darkgrey", "#A9A9A9"),
+    ("darkkhaki", "#BDB76B"),
+    ("darkmagenta", "#8B008B"),
+    ("darkolivegreen", "#556B2F"),
+    ("darkorange", "#FF8C00"),
+    ("darkorchid", "#9932CC"),
+    ("darkred", "#8B0000"),
+    ("darksalmon", "#E9967A"),
+    ("darkseagreen", "#8FBC8F"),
+    ("darkslateblue", "#483D8B"),
+    ("darkslategray", "#2F4F4F"),
+    ("darkslategrey", "#2F4F4F"),
+    ("darkturquoise", "#00CED1"),
+    ("darkviolet", "#9400D3"),
+    ("deeppink", "#FF1493"),
+    ("deepskyblue", "#00BFFF"),
+    ("dimgray", "#696969"),
+    ("dimgrey", "#696969"),
+    ("dodgerblue", "#1E90FF"),
+    ("firebrick", "#B22222"),
+    ("floralwhite", "#FFFAF0"),
+    ("forestgreen", "#228B22"),
+    ("fuchsia", "#FF00FF"),
+    ("gainsboro", "#DCDCDC"),
+    ("ghostwhite", "#F8F8FF"),
+    ("gold", "#FFD700"),
+    ("goldenrod", "#DAA520"),
+    ("gray", "#808080"),
+    ("green", "#008000"),
+    ("greenyellow", "#ADFF2F"),
+    ("grey", "#808080"),
+    ("honeydew", "#F0FFF0"),
+    ("hotpink", "#FF69B4"),
+    ("indianred", "#CD5C5C"),
+    ("indigo", "#4B0082"),
+    ("ivory", "#FFFFF0"),
+    ("khaki", "#F0E68C"),
+    ("lavender", "#E6E6FA"),
+    ("lavenderblush", "#FFF0F5"),
+    ("lawngreen", "#7CFC00"),
+    ("lemonchiffon", "#FFFACD"),
+    ("lightblue", "#ADD8E6"),
+    ("lightcoral", "#F08080"),
+    ("lightcyan", "#E0FFFF"),
+    ("lightgoldenrodyellow", "#FAFAD2"),
+    ("lightgray", "#D3D3D3"),
+    ("lightgreen", "#90EE90"),
+    ("lightgrey", "#D3D3D3"),
+    ("lightpink", "#FFB6C1"),
+    ("lightsalmon", "#FFA07A"),
+    ("lightseagreen", "#20B2AA"),
+    ("lightskyblue", "#87CEFA"),
+    ("lightslategray", "#778899"),
+    ("lightslategrey", "#778899"),
+    ("lightsteelblue", "#B0C4DE"),
+    ("lightyellow", "#FFFFE0"),
+    ("lime", "#00FF00"),
+    ("limegreen", "#32CD32"),
+    ("linen", "#FAF0E6"),
+    ("magenta", "#FF00FF"),
+    ("maroon", "#800000"),
+    ("mediumaquamarine", "#66CDAA"),
+    ("mediumblue", "#0000CD"),
+    ("mediumorchid", "#BA55D3"),
+    ("mediumpurple", "#9370DB"),
+    ("mediumseagreen", "#3CB371"),
+    ("mediumslateblue", "#7B68EE"),
+    ("mediumspringgreen", "#00FA9A"),
+    ("mediumturquoise", "#48D1CC"),
+    ("mediumvioletred", "#C71585"),
+    ("midnightblue", "#191970"),
+    ("mintcream", "#F5FFFA"),
+    ("mistyrose", "#FFE4E1"),
+    ("moccasin", "#FFE4B5"),
+    ("navajowhite", "#FFDEAD"),
+    ("navy", "#000080"),
+    ("oldlace", "#FDF5E6"),
+    ("olive", "#808000"),
+    ("olivedrab", "#6B8E23"),
+    ("orange", "#FFA500"),
+    ("orangered", "#FF4500"),
+    ("orchid", "#DA70D6"),
+    ("palegoldenrod", "#EEE8AA"),
+    ("palegreen", "#98FB98"),
+    ("paleturquoise", "#AFEEEE"),
+    ("palevioletred", "#DB7093"),
+    ("papayawhip", "#FFEFD5"),
+    ("peachpuff", "#FFDAB9"),
+    ("peru", "#CD853F"),
+    ("pink", "#FFC0CB"),
+    ("plum", "#DDA0DD"),
+    ("powderblue", "#B0E0E6"),
+    ("purple", "#800080"),
+    ("rebeccapurple", "#663399"),
+    ("red", "#FF0000"),
+    ("rosybrown", "#BC8F8F"),
+    ("royalblue", "#4169E1"),
+    ("saddlebrown", "#8B4513"),
+    ("salmon", "#FA8072"),
+    ("sandybrown", "#F4A460"),
+    ("seagreen", "#2E8B57"),
+    ("seashell", "#FFF5EE"),
+    ("sienna", "#A0522D"),
+    ("silver", "#C0C0C0"),
+    ("skyblue", "#87CEEB"),
+    ("slateblue", "#6A5ACD"),
+    ("slategray", "#708090"),
+    ("slategrey", "#708090"),
+    ("snow", "#FFFAFA"),
+    ("springgreen", "#00FF7F"),
+    ("steelblue", "#4682B4"),
+    ("tan", "#D2B48C"),
+    ("teal", "#008080"),
+    ("thistle", "#D8BFD8"),
+    ("tomato", "#FF6347"),
+    ("turquoise", "#40E0D0"),
+    ("violet", "#EE82EE"),
+    ("wheat", "#F5DEB3"),
+    ("white", "#FFFFFF"),
+    ("whitesmoke", "#F5F5F5"),
+    ("yellow", "#FFFF00"),
+    ("yellowgreen", "#9ACD32"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_color_formats() {
+        assert_eq!("Black".parse::<Color>().unwrap(), Color::Named("black"));
+        assert_eq!("#f00".parse::<Color>().unwrap(), Color::Rgb(255, 0, 0));
+        assert_eq!("#00e000".parse::<Color>().unwrap(), Color::Rgb(0, 224, 0));
+        assert_eq!("rgb(0, 0, 255)".parse::<Color>().unwrap(), Color::Rgb(0, 0, 255));
+        assert_eq!("rgb(100% 0% 50%)".parse::<Color>().unwrap(), Color::Rgb(255, 0, 128));
+        assert_eq!("lightburn:05".parse::<Color>().unwrap(), Color::Rgb(255, 128, 0));
+    }
+
+    #[test]
+    fn test_normalised_form() {
+        assert_eq!(normalise_color(" Blue ").unwrap(), "blue");
+        assert_eq!(normalise_color("#abc").unwrap(), "#AABBCC");
+        assert_eq!(normalise_color("rgb(255,0,0)").unwrap(), "#FF0000");
+        assert_eq!(Color::Named("rebeccapurple").to_hex(), "#663399");
+    }
+
+    #[test]
+    fn test_same_color() {
+        assert!(same_color("blue", "#0000FF"));
+        assert!(same_color("aqua", "cyan"));
+        assert!(same_color("#000", "rgb(0, 0, 0)"));
+        assert!(!same_color("blue", "black"));
+        assert!(same_color("url(#a)", "url(#a)"));
+        assert!(!same_color("url(#a)", "blue"));
+    }
+
+    #[test]
+    fn test_invalid_colors() {
+        for invalid in ["blu", "#12", "#ggg", "rgb(256, 0, 0)", "rgb(1, 2)", "lightburn:30", "acme:1"] {
+            assert!(invalid.parse::<Color>().is_err(), "{} should be invalid", invalid);
+        }
+        assert!("blu".parse::<Color>().unwrap_err().contains("Unknown color 'blu'"));
+    }
+
+    #[test]
+    fn test_palettes() {
+        assert_eq!(Palette::LightBurn.colors().len(), 30);
+        assert_eq!("xTool Creative Space".parse::<Palette>().unwrap(), Palette::XTool);
+        for palette in Palette::ALL {
+            let colors = palette.colors();
+            let mut hexes: Vec<String> = colors.iter().map(Color::to_hex).collect();
+            hexes.sort();
+            hexes.dedup();
+            assert_eq!(hexes.len(), colors.len(), "{} has duplicate colors", palette);
+            assert_eq!(palette.color("0").unwrap().to_hex(), "#000000");
+        }
+    }
+}
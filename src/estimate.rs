@@ -0,0 +1,288 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::color::same_color;
+use crate::rack::ordering::travel_length;
+use crate::rack::GeneratedSvg;
+
+/// Speeds and prices of a laser cutter
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MachineProfile {
+    /// Cutting speed in mm/s
+    pub cut_speed: f32,
+    /// Cutting speed in mm/s per line color, overriding `cut_speed`. Colors match however they
+    /// are written, e.g. `blue` and `#0000FF`.
+    pub cut_speeds: BTreeMap<String, f32>,
+    /// Speed of moving between cuts in mm/s
+    pub travel_speed: f32,
+    /// Time to pierce the material at the start of every cut, in seconds
+    pub pierce_delay: f32,
+    /// Price of laser time per minute
+    pub price_per_minute: f32,
+}
+
+impl Default for MachineProfile {
+    fn default() -> Self {
+        MachineProfile {
+            cut_speed: 10.0,
+            cut_speeds: BTreeMap::new(),
+            travel_speed: 200.0,
+            pierce_delay: 0.2,
+            price_per_minute: 0.0,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum MachineProfileError {
+    Io(std::io::Error),
+    Toml(String),
+    /// Values the estimate can't be calculated with
+    Invalid(Vec<String>),
+}
+
+impl fmt::Display for MachineProfileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MachineProfileError::Io(error) => write!(f, "Could not read machine profile: {}", error),
+            MachineProfileError::Toml(error) => write!(f, "Invalid machine profile: {}", error),
+            MachineProfileError::Invalid(problems) => {
+                write!(f, "Invalid machine profile: {}", problems.join(", "))
+            }
+        }
+    }
+}
+
+impl std::error::Error for MachineProfileError {}
+
+impl MachineProfile {
+    /// Load profile from TOML file, missing values use defaults
+    pub fn load(path: &Path) -> Result<MachineProfile, MachineProfileError> {
+        let content = fs::read_to_string(path).map_err(MachineProfileError::Io)?;
+        MachineProfile::from_toml(&content)
+    }
+
+    pub fn from_toml(content: &str) -> Result<MachineProfile, MachineProfileError> {
+        let profile: MachineProfile =
+            toml::from_str(content).map_err(|e| MachineProfileError::Toml(e.to_string()))?;
+        profile.validate().map_err(MachineProfileError::Invalid)?;
+        Ok(profile)
+    }
+
+    /// Check that every speed is positive, so times of cuts and moves are finite, and that the
+    /// pierce delay and price aren't negative
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let is_positive = |speed: f32| speed.is_finite() && speed > 0.0;
+        let is_not_negative = |value: f32| value.is_finite() && value >= 0.0;
+        let mut problems = Vec::new();
+
+        if !is_positive(self.cut_speed) {
+            problems.push("cut_speed must be positive".to_string());
+        }
+        for (color, speed) in &self.cut_speeds {
+            if !is_positive(*speed) {
+                problems.push(format!("cut_speeds of {} must be positive", color));
+            }
+        }
+        if !is_positive(self.travel_speed) {
+            problems.push("travel_speed must be positive".to_string());
+        }
+        if !is_not_negative(self.pierce_delay) {
+            problems.push("pierce_delay must not be negative".to_string());
+        }
+        if !is_not_negative(self.price_per_minute) {
+            problems.push("price_per_minute must not be negative".to_string());
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(problems)
+        }
+    }
+
+    fn cut_speed_for(&self, color: &str) -> f32 {
+        self.cut_speeds
+            .iter()
+            .find(|(key, _)| same_color(key, color))
+            .map_or(self.cut_speed, |(_, speed)| *speed)
+    }
+}
+
+/// Predicted duration of a laser job. Lengths are in mm and times in seconds.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JobEstimate {
+    pub cut_length: f32,
+    pub travel_length: f32,
+    pub pierces: usize,
+    pub cut_time: f32,
+    pub travel_time: f32,
+    pub pierce_time: f32,
+    pub price_per_minute: f32,
+}
+
+impl JobEstimate {
+    pub fn total_time(&self) -> f32 {
+        self.cut_time + self.travel_time + self.pierce_time
+    }
+
+    /// Price of the laser time
+    pub fn laser_cost(&self) -> f32 {
+        self.total_time() / 60.0 * self.price_per_minute
+    }
+
+    /// Laser time and material together
+    pub fn total_cost(&self, sheets: usize, price_per_sheet: f32) -> f32 {
+        self.laser_cost() + sheets as f32 * price_per_sheet
+    }
+}
+
+/// Estimate cutting the generated rack on given machine.
+///
+/// Cuts are run in the order of the SVG, see [`crate::rack::ordering::cutting_order`].
+/// Every cut is one pierce.
+pub fn estimate_job(generated: &GeneratedSvg, machine: &MachineProfile) -> JobEstimate {
+    let cut_time = generated
+        .stats
+        .cut_length_by_color
+        .iter()
+        .map(|(color, length)| length / machine.cut_speed_for(color))
+        .sum();
+
+    let travel_length = travel_length(&generated.parts);
+    let pierces = generated
+        .parts
+        .iter()
+        .map(|part| part.cuts.len())
+        .sum();
+
+    JobEstimate {
+        cut_length: generated.stats.total_cut_length(),
+        travel_length,
+        pierces,
+        cut_time,
+        travel_time: travel_length / machine.travel_speed,
+        pierce_time: pierces as f32 * machine.pierce_delay,
+        price_per_minute: machine.price_per_minute,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalogue::{builtin_containers, find_container_in};
+    use crate::project::{ProjectContainer, RackProject};
+    use crate::units::Length;
+
+    fn generated() -> GeneratedSvg {
+        RackProject::new(
+            1,
+            1,
+            Length::millimeters(3.0),
+            ProjectContainer::Inline(
+                find_container_in(builtin_containers(), "smartstore-classic_1").unwrap(),
+            ),
+        )
+        .generate()
+        .unwrap()
+    }
+
+    #[test]
+    fn test_estimate_job() {
+        let generated = generated();
+        let machine = MachineProfile {
+            cut_speed: 10.0,
+            cut_speeds: BTreeMap::new(),
+            travel_speed: 100.0,
+            pierce_delay: 0.5,
+            price_per_minute: 1.5,
+        };
+
+        let estimate = estimate_job(&generated, &machine);
+
+        // 2 wings, 2 covers without holes and 2 side panels with 4 holes each
+        assert_eq!(estimate.pierces, 14);
+        assert_eq!(estimate.pierce_time, 7.0);
+        assert_eq!(estimate.cut_length, 3984.0);
+        assert!((estimate.cut_time - 398.4).abs() < 0.001);
+        assert!(estimate.travel_length > 0.0);
+        assert_eq!(estimate.travel_time, estimate.travel_length / 100.0);
+        assert_eq!(estimate.laser_cost(), estimate.total_time() / 60.0 * 1.5);
+        assert_eq!(estimate.total_cost(2, 10.0), estimate.laser_cost() + 20.0);
+    }
+
+    #[test]
+    fn test_cut_speed_per_color() {
+        let generated = generated();
+        let mut machine = MachineProfile::default();
+        machine.cut_speeds.insert("black".to_string(), 20.0);
+
+        let estimate = estimate_job(&generated, &machine);
+
+        let holes = generated.stats.cut_length_by_color["black"];
+        let outlines = generated.stats.cut_length_by_color["blue"];
+        assert_eq!(estimate.cut_time, holes / 20.0 + outlines / 10.0);
+    }
+
+    #[test]
+    fn test_cut_speed_color_written_differently() {
+        let generated = generated();
+        let mut machine = MachineProfile::default();
+        machine.cut_speeds.insert("#000".to_string(), 20.0);
+        machine.cut_speeds.insert("#0000FF".to_string(), 40.0);
+
+        let estimate = estimate_job(&generated, &machine);
+
+        let holes = generated.stats.cut_length_by_color["black"];
+        let outlines = generated.stats.cut_length_by_color["blue"];
+        assert_eq!(estimate.cut_time, holes / 20.0 + outlines / 40.0);
+    }
+
+    #[test]
+    fn test_partial_profile_uses_defaults() {
+        let machine = MachineProfile::from_toml("cut_speed = 25\nprice_per_minute = 0.8").unwrap();
+
+        assert_eq!(machine.cut_speed, 25.0);
+        assert_eq!(machine.travel_speed, MachineProfile::default().travel_speed);
+    }
+
+    #[test]
+    fn test_speeds_must_be_positive() {
+        let error = MachineProfile::from_toml(
+            "cut_speed = 0\ntravel_speed = -100\n\n[cut_speeds]\nblack = 20\nblue = 0\n",
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "Invalid machine profile: cut_speed must be positive, cut_speeds of blue must be positive, travel_speed must be positive"
+        );
+        assert!(MachineProfile::default().validate().is_ok());
+    }
+
+    #[test]
+    fn test_pierce_delay_and_price_must_not_be_negative() {
+        let error = MachineProfile::from_toml("pierce_delay = -0.5
+price_per_minute = -1
+").unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "Invalid machine profile: pierce_delay must not be negative, price_per_minute must not be negative"
+        );
+        let profile = MachineProfile {
+            pierce_delay: f32::NAN,
+            price_per_minute: f32::INFINITY,
+            ..MachineProfile::default()
+        };
+        assert_eq!(profile.validate().unwrap_err().len(), 2);
+        assert!(MachineProfile::from_toml("pierce_delay = 0
+price_per_minute = 0
+").is_ok());
+    }
+}
@@ -0,0 +1,157 @@
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use svg::node::element::tag::Type;
+use svg::node::element::Element;
+use svg::node::Text;
+use svg::parser::Event;
+use svg::Node;
+
+use crate::project::RackProject;
+
+/// Id of the `<metadata>` element holding the generation parameters
+pub const METADATA_ID: &str = "container-rack";
+const GENERATOR: &str = env!("CARGO_PKG_NAME");
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Generation parameters embedded to generated SVG
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DesignMetadata {
+    pub generator: String,
+    /// Version of the library that generated the SVG
+    pub version: String,
+    pub project: RackProject,
+}
+
+impl DesignMetadata {
+    /// Metadata of a design generated with this version of the library
+    pub fn new(project: RackProject) -> DesignMetadata {
+        DesignMetadata {
+            generator: GENERATOR.to_string(),
+            version: VERSION.to_string(),
+            project,
+        }
+    }
+
+    /// Whether the design was generated with an other version than this one
+    pub fn is_outdated(&self) -> bool {
+        self.version != VERSION
+    }
+
+    /// `<metadata>` element with the parameters as JSON
+    pub fn to_element(&self) -> Element {
+        let mut element = Element::new("metadata");
+        element.assign("id", METADATA_ID);
+        element.append(Text::new(
+            serde_json::to_string(self).expect("metadata is always serializable"),
+        ));
+        element
+    }
+}
+
+#[derive(Debug)]
+pub enum MetadataError {
+    Io(std::io::Error),
+    Svg(String),
+    /// SVG has no metadata from this library
+    Missing,
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for MetadataError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MetadataError::Io(error) => write!(f, "Could not read SVG: {}", error),
+            MetadataError::Svg(error) => write!(f, "Invalid SVG: {}", error),
+            MetadataError::Missing => write!(f, "SVG has no container rack metadata"),
+            MetadataError::Json(error) => write!(f, "Invalid container rack metadata: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for MetadataError {}
+
+/// Read generation parameters from SVG file
+pub fn inspect_file(path: &Path) -> Result<DesignMetadata, MetadataError> {
+    let content = fs::read_to_string(path).map_err(MetadataError::Io)?;
+    inspect(&content)
+}
+
+/// Read generation parameters from SVG content
+pub fn inspect(svg_content: &str) -> Result<DesignMetadata, MetadataError> {
+    let mut inside_metadata = false;
+
+    for event in svg::read(svg_content).map_err(|e| MetadataError::Svg(e.to_string()))? {
+        match event {
+            Event::Error(error) => return Err(MetadataError::Svg(error.to_string())),
+            Event::Tag("metadata", Type::Start, attributes) => {
+                inside_metadata = attributes
+                    .get("id")
+                    .is_some_and(|id| *id == METADATA_ID);
+            }
+            Event::Tag("metadata", _, _) => inside_metadata = false,
+            Event::Text(text) if inside_metadata => {
+                return serde_json::from_str(&unescape(text.trim())).map_err(MetadataError::Json);
+            }
+            _ => {}
+        }
+    }
+
+    Err(MetadataError::Missing)
+}
+
+/// Reverse XML escaping of text content
+fn unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project::ProjectContainer;
+    use crate::units::Length;
+
+    #[test]
+    fn test_metadata_round_trip() {
+        let mut project = RackProject::new(
+            2,
+            3,
+            Length::millimeters(4.0),
+            ProjectContainer::Key("smartstore-classic_1".to_string()),
+        );
+        project.output.filename = Some("<black & white>".to_string());
+        let metadata = DesignMetadata::new(project);
+        let document = svg::Document::new().add(metadata.to_element());
+
+        let inspected = inspect(&document.to_string()).unwrap();
+
+        assert_eq!(inspected, metadata);
+        assert!(!inspected.is_outdated());
+    }
+
+    #[test]
+    fn test_missing_metadata() {
+        let document = svg::Document::new().add(Element::new("metadata"));
+
+        assert!(matches!(inspect(&document.to_string()), Err(MetadataError::Missing)));
+    }
+
+    #[test]
+    fn test_outdated_version() {
+        let mut metadata = DesignMetadata::new(RackProject::new(
+            1,
+            1,
+            Length::millimeters(4.0),
+            ProjectContainer::Key("smartstore-classic_1".to_string()),
+        ));
+        metadata.version = "0.3.0".to_string();
+
+        assert!(metadata.is_outdated());
+    }
+}
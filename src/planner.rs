@@ -0,0 +1,231 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::rack::{assembled_dimensions, AssembledDimensions, ContainerDimensions};
+
+/// Space available for the rack, e.g. a shelf cavity. All measurements are in mm.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AvailableSpace {
+    pub width: f32,
+    pub height: f32,
+    pub depth: f32,
+}
+
+/// How the rack stands in the available space
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Orientation {
+    /// Containers are pulled out from the front of the space
+    Front,
+    /// Rack is turned a quarter around, containers are pulled out from the side of the space
+    Side,
+}
+
+impl fmt::Display for Orientation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Orientation::Front => write!(f, "front"),
+            Orientation::Side => write!(f, "side"),
+        }
+    }
+}
+
+/// Row and column combination that fits the available space
+#[derive(Debug, Clone)]
+pub struct RackPlan {
+    pub rows: usize,
+    pub columns: usize,
+    pub orientation: Orientation,
+    pub assembled_dimensions: AssembledDimensions,
+    /// Space left over around the rack, measured along the width, height and depth of the
+    /// available space
+    pub leftover: AvailableSpace,
+}
+
+impl RackPlan {
+    pub fn container_count(&self) -> usize {
+        self.rows * self.columns
+    }
+}
+
+/// Every row and column combination that fits the available space. Side orientation is
+/// included only when `include_rotated` is set.
+///
+/// Plans with most containers come first, then plans with least leftover space. Nothing
+/// fits when the container or the material has no size.
+pub fn plan(
+    space: &AvailableSpace,
+    container: &ContainerDimensions,
+    material_thickness: f32,
+    include_rotated: bool,
+) -> Vec<RackPlan> {
+    let mut orientations = vec![Orientation::Front];
+    if include_rotated {
+        orientations.push(Orientation::Side);
+    }
+
+    let mut plans = vec![];
+    let sizes = [container.width, container.height, material_thickness, space.height];
+    if !sizes.iter().all(|size| size.is_finite() && *size > 0.0) {
+        return plans;
+    }
+    // Every row and column takes at least the height or width of a container
+    let max_rows = (space.height / container.height) as usize;
+
+    for orientation in orientations {
+        // Width and depth of the space as seen from the front of the rack
+        let (width, depth) = match orientation {
+            Orientation::Front => (space.width, space.depth),
+            Orientation::Side => (space.depth, space.width),
+        };
+        if container.depth > depth || !width.is_finite() {
+            continue;
+        }
+        let max_columns = (width / container.width) as usize;
+
+        for rows in 1..=max_rows {
+            let height = assembled_dimensions(rows, 1, material_thickness, container).height;
+            if height > space.height {
+                break;
+            }
+
+            for columns in 1..=max_columns {
+                let assembled = assembled_dimensions(rows, columns, material_thickness, container);
+                if assembled.width > width {
+                    break;
+                }
+
+                let leftover = match orientation {
+                    Orientation::Front => AvailableSpace {
+                        width: space.width - assembled.width,
+                        height: space.height - assembled.height,
+                        depth: space.depth - assembled.depth,
+                    },
+                    Orientation::Side => AvailableSpace {
+                        width: space.width - assembled.depth,
+                        height: space.height - assembled.height,
+                        depth: space.depth - assembled.width,
+                    },
+                };
+                plans.push(RackPlan {
+                    rows,
+                    columns,
+                    orientation,
+                    assembled_dimensions: assembled,
+                    leftover,
+                });
+            }
+        }
+    }
+
+    plans.sort_by(|a, b| {
+        b.container_count()
+            .cmp(&a.container_count())
+            .then(rack_volume(b).total_cmp(&rack_volume(a)))
+    });
+    plans
+}
+
+/// Volume taken by the rack, the bigger the rack the less space is left over
+fn rack_volume(plan: &RackPlan) -> f32 {
+    let dimensions = &plan.assembled_dimensions;
+    dimensions.width * dimensions.height * dimensions.depth
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalogue::{builtin_containers, find_container_in};
+
+    fn classic_1() -> ContainerDimensions {
+        find_container_in(builtin_containers(), "smartstore-classic_1").unwrap().dimensions
+    }
+
+    #[test]
+    fn test_plan_for_shelf() {
+        let space = AvailableSpace {
+            width: 800.0,
+            height: 250.0,
+            depth: 600.0,
+        };
+
+        let plans = plan(&space, &classic_1(), 4.0, false);
+        let best = &plans[0];
+
+        // Columns are 174mm wide plus 4mm walls, rows are 56mm high plus 8mm top and bottom
+        assert_eq!((best.rows, best.columns), (4, 4));
+        assert_eq!(best.assembled_dimensions.width, 716.0);
+        assert_eq!(best.assembled_dimensions.height, 232.0);
+        assert_eq!(best.leftover.width, 84.0);
+        assert_eq!(best.leftover.height, 18.0);
+        assert_eq!(best.leftover.depth, 390.0);
+        assert_eq!(plans.len(), 16);
+        assert!(plans
+            .iter()
+            .all(|plan| plan.leftover.width >= 0.0 && plan.leftover.height >= 0.0));
+    }
+
+    #[test]
+    fn test_plans_are_sorted_by_container_count() {
+        let space = AvailableSpace {
+            width: 800.0,
+            height: 250.0,
+            depth: 600.0,
+        };
+
+        let plans = plan(&space, &classic_1(), 4.0, true);
+
+        assert!(plans
+            .windows(2)
+            .all(|pair| pair[0].container_count() >= pair[1].container_count()));
+    }
+
+    #[test]
+    fn test_rotated_plan_for_narrow_deep_space() {
+        let space = AvailableSpace {
+            width: 220.0,
+            height: 100.0,
+            depth: 600.0,
+        };
+
+        assert_eq!(plan(&space, &classic_1(), 4.0, false).len(), 1);
+
+        let plans = plan(&space, &classic_1(), 4.0, true);
+        let best = &plans[0];
+
+        assert_eq!(best.orientation, Orientation::Side);
+        assert_eq!((best.rows, best.columns), (1, 3));
+        assert_eq!(best.leftover.width, 10.0);
+        assert_eq!(best.leftover.depth, 62.0);
+    }
+
+    #[test]
+    fn test_nothing_fits() {
+        let space = AvailableSpace {
+            width: 100.0,
+            height: 100.0,
+            depth: 100.0,
+        };
+
+        assert!(plan(&space, &classic_1(), 4.0, true).is_empty());
+    }
+
+    #[test]
+    fn test_sizeless_container_or_material_fits_nothing() {
+        let space = AvailableSpace {
+            width: 800.0,
+            height: 250.0,
+            depth: 600.0,
+        };
+        let mut flat = classic_1();
+        flat.height = 0.0;
+        let mut narrow = classic_1();
+        narrow.width = -170.0;
+
+        assert!(plan(&space, &flat, 4.0, true).is_empty());
+        assert!(plan(&space, &narrow, 4.0, true).is_empty());
+        assert!(plan(&space, &classic_1(), 0.0, true).is_empty());
+        assert!(plan(&space, &classic_1(), f32::NAN, true).is_empty());
+    }
+}
@@ -0,0 +1,759 @@
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::catalogue::{find_container, ContainerLookupError};
+use crate::color::{Color, Palette};
+use crate::rack::operations::{OperationStyle, OperationStyles};
+use crate::rack::output::LaserSoftware;
+use crate::rack::tabs::{micro_tab_capacity, MicroTabs};
+use crate::rack::{
+    generate_svg_with_options, Container, ContainerDimensions, GeneratedSvg, GenerationOptions,
+};
+use crate::units::{Length, Unit};
+
+const DEFAULT_PRIMARY_COLOR: Color = Color::Named("black");
+const DEFAULT_SECONDARY_COLOR: Color = Color::Named("blue");
+
+/// Everything needed to generate the same rack again. Saved as TOML or JSON.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RackProject {
+    pub rows: usize,
+    pub columns: usize,
+    pub material_thickness: Length,
+    pub container: ProjectContainer,
+    #[serde(default)]
+    pub colors: ProjectColors,
+    #[serde(default)]
+    pub layers: ProjectLayers,
+    #[serde(default)]
+    pub output: OutputSettings,
+    #[serde(default)]
+    pub cutting: CuttingSettings,
+}
+
+/// Container of the project: key of a supported container, a full container or only its
+/// dimensions
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ProjectContainer {
+    Key(String),
+    Inline(Container),
+    Dimensions(ContainerDimensions),
+}
+
+/// Colors of the operations. Colors are checked and normalised when read.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProjectColors {
+    /// Color of the lines cut first, i.e. holes
+    #[serde(default = "default_primary_color")]
+    pub primary: Color,
+    /// Color of the lines cut last, i.e. outlines
+    #[serde(default = "default_secondary_color")]
+    pub secondary: Color,
+    #[serde(default = "default_score_color")]
+    pub score: Color,
+    #[serde(default = "default_engrave_fill_color")]
+    pub engrave_fill: Color,
+    #[serde(default = "default_engrave_text_color")]
+    pub engrave_text: Color,
+}
+
+impl Default for ProjectColors {
+    fn default() -> Self {
+        ProjectColors {
+            primary: default_primary_color(),
+            secondary: default_secondary_color(),
+            score: default_score_color(),
+            engrave_fill: default_engrave_fill_color(),
+            engrave_text: default_engrave_text_color(),
+        }
+    }
+}
+
+impl ProjectColors {
+    /// Every color from given palette, in the order operations are listed in the palette:
+    /// holes, outlines, score, engrave fill and engrave text
+    pub fn from_palette(palette: Palette) -> ProjectColors {
+        let colors = palette.colors();
+
+        ProjectColors {
+            primary: colors[0],
+            secondary: colors[1],
+            score: colors[2],
+            engrave_fill: colors[3],
+            engrave_text: colors[4],
+        }
+    }
+
+    /// Colors of the operation styles. Styles can have any SVG color, colors this library
+    /// doesn't understand are an error as the project couldn't draw the same lines.
+    pub fn from_styles(styles: &OperationStyles) -> Result<ProjectColors, String> {
+        let color = |style: &OperationStyle| style.color.parse::<Color>();
+
+        Ok(ProjectColors {
+            primary: color(&styles.cut_inner)?,
+            secondary: color(&styles.cut_outer)?,
+            score: color(&styles.score)?,
+            engrave_fill: color(&styles.engrave_fill)?,
+            engrave_text: color(&styles.engrave_text)?,
+        })
+    }
+}
+
+fn default_primary_color() -> Color {
+    DEFAULT_PRIMARY_COLOR
+}
+
+fn default_secondary_color() -> Color {
+    DEFAULT_SECONDARY_COLOR
+}
+
+/// Default color of an operation style, the defaults are valid colors
+fn default_style_color(style: OperationStyle) -> Color {
+    style.color.parse().expect("default colors are valid")
+}
+
+fn default_score_color() -> Color {
+    default_style_color(OperationStyles::default().score)
+}
+
+fn default_engrave_fill_color() -> Color {
+    default_style_color(OperationStyles::default().engrave_fill)
+}
+
+fn default_engrave_text_color() -> Color {
+    default_style_color(OperationStyles::default().engrave_text)
+}
+
+/// Names of the SVG layers of each operation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProjectLayers {
+    #[serde(default = "default_cut_inner_layer")]
+    pub cut_inner: String,
+    #[serde(default = "default_cut_outer_layer")]
+    pub cut_outer: String,
+    #[serde(default = "default_score_layer")]
+    pub score: String,
+    #[serde(default = "default_engrave_fill_layer")]
+    pub engrave_fill: String,
+    #[serde(default = "default_engrave_text_layer")]
+    pub engrave_text: String,
+}
+
+impl Default for ProjectLayers {
+    fn default() -> Self {
+        ProjectLayers {
+            cut_inner: default_cut_inner_layer(),
+            cut_outer: default_cut_outer_layer(),
+            score: default_score_layer(),
+            engrave_fill: default_engrave_fill_layer(),
+            engrave_text: default_engrave_text_layer(),
+        }
+    }
+}
+
+fn default_cut_inner_layer() -> String {
+    OperationStyles::default().cut_inner.layer
+}
+
+fn default_cut_outer_layer() -> String {
+    OperationStyles::default().cut_outer.layer
+}
+
+fn default_score_layer() -> String {
+    OperationStyles::default().score.layer
+}
+
+fn default_engrave_fill_layer() -> String {
+    OperationStyles::default().engrave_fill.layer
+}
+
+fn default_engrave_text_layer() -> String {
+    OperationStyles::default().engrave_text.layer
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct OutputSettings {
+    /// Unit of the SVG document size, the unit of the profile when not set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unit: Option<Unit>,
+    /// File name without `.svg` extension
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filename: Option<String>,
+    /// Laser software the SVG is written for
+    #[serde(default)]
+    pub profile: LaserSoftware,
+    /// Engrave the label of every part on it
+    #[serde(default)]
+    pub engrave_labels: bool,
+}
+
+impl OutputSettings {
+    /// Unit of the SVG document: the one set, the unit the profile expects or millimeters
+    pub fn document_unit(&self) -> Unit {
+        self.unit
+            .or(self.profile.profile().unit)
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CuttingSettings {
+    /// Place similar parts edge to edge and cut their shared edges only once
+    #[serde(default)]
+    pub common_line: bool,
+    /// Tabs holding parts in the sheet
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub micro_tabs: Option<MicroTabs>,
+}
+
+#[derive(Debug)]
+pub enum ProjectError {
+    Io(std::io::Error),
+    Toml(String),
+    Json(serde_json::Error),
+    Container(ContainerLookupError),
+    /// Parameters of the rack which can't be generated
+    Invalid(Vec<String>),
+    /// Generation stopped unexpectedly
+    Generation(String),
+}
+
+impl fmt::Display for ProjectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProjectError::Io(error) => write!(f, "Could not access project file: {}", error),
+            ProjectError::Toml(error) => write!(f, "Invalid TOML project: {}", error),
+            ProjectError::Json(error) => write!(f, "Invalid JSON project: {}", error),
+            ProjectError::Container(error) => write!(f, "{}", error),
+            ProjectError::Invalid(problems) => write!(f, "Invalid project: {}", problems.join(", ")),
+            ProjectError::Generation(error) => write!(f, "Generation failed: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for ProjectError {}
+
+impl RackProject {
+    /// Project with default colors, layers, output and cutting settings
+    pub fn new(
+        rows: usize,
+        columns: usize,
+        material_thickness: Length,
+        container: ProjectContainer,
+    ) -> RackProject {
+        RackProject {
+            rows,
+            columns,
+            material_thickness,
+            container,
+            colors: ProjectColors::default(),
+            layers: ProjectLayers::default(),
+            output: OutputSettings::default(),
+            cutting: CuttingSettings::default(),
+        }
+    }
+
+    /// Load project from file, `.json` files are read as JSON and everything else as TOML
+    pub fn load(path: &Path) -> Result<RackProject, ProjectError> {
+        let content = fs::read_to_string(path).map_err(ProjectError::Io)?;
+
+        if is_json(path) {
+            RackProject::from_json(&content)
+        } else {
+            RackProject::from_toml(&content)
+        }
+    }
+
+    /// Save project to file, `.json` files are written as JSON and everything else as TOML
+    pub fn save(&self, path: &Path) -> Result<(), ProjectError> {
+        let content = if is_json(path) {
+            self.to_json()?
+        } else {
+            self.to_toml()?
+        };
+
+        fs::write(path, content).map_err(ProjectError::Io)
+    }
+
+    pub fn from_toml(content: &str) -> Result<RackProject, ProjectError> {
+        let project: RackProject =
+            toml::from_str(content).map_err(|e| ProjectError::Toml(e.to_string()))?;
+        project.validate()?;
+        Ok(project)
+    }
+
+    pub fn to_toml(&self) -> Result<String, ProjectError> {
+        toml::to_string_pretty(self).map_err(|e| ProjectError::Toml(e.to_string()))
+    }
+
+    pub fn from_json(content: &str) -> Result<RackProject, ProjectError> {
+        let project: RackProject = serde_json::from_str(content).map_err(ProjectError::Json)?;
+        project.validate()?;
+        Ok(project)
+    }
+
+    pub fn to_json(&self) -> Result<String, ProjectError> {
+        serde_json::to_string_pretty(self).map_err(ProjectError::Json)
+    }
+
+    /// Check that the rack has boxes, the material has a thickness, a container given by its
+    /// dimensions can hold a rack and the output file name stays in the output directory
+    pub fn validate(&self) -> Result<(), ProjectError> {
+        let mut problems = Vec::new();
+
+        if self.rows == 0 {
+            problems.push("Rows must be at least 1".to_string());
+        }
+        if self.columns == 0 {
+            problems.push("Columns must be at least 1".to_string());
+        }
+        if self.material_thickness.to_mm() <= 0.0 || !self.material_thickness.to_mm().is_finite() {
+            problems.push("Material thickness must be positive".to_string());
+        }
+        if let Some(Err(problem)) = self.cutting.micro_tabs.as_ref().map(MicroTabs::validate) {
+            problems.push(problem);
+        }
+        let dimensions = match &self.container {
+            ProjectContainer::Key(_) => None,
+            ProjectContainer::Inline(container) => Some(&container.dimensions),
+            ProjectContainer::Dimensions(dimensions) => Some(dimensions),
+        };
+        if let Some(Err(container_problems)) = dimensions.map(ContainerDimensions::validate) {
+            problems.extend(container_problems.into_iter().map(|problem| format!("Container: {}", problem)));
+        }
+        if let Some(filename) = &self.output.filename {
+            if !is_plain_filename(filename) {
+                problems.push(format!("Output file name {:?} must not contain directories", filename));
+            }
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(ProjectError::Invalid(problems))
+        }
+    }
+
+    /// Container of the project, keys are looked up from supported containers
+    pub fn resolve_container(&self) -> Result<Container, ProjectError> {
+        match &self.container {
+            ProjectContainer::Key(key) => find_container(key).map_err(ProjectError::Container),
+            ProjectContainer::Inline(container) => Ok(container.clone()),
+            ProjectContainer::Dimensions(dimensions) => Ok(Container {
+                vendor: "Custom".to_string(),
+                model: "Container".to_string(),
+                description: String::new(),
+                links: vec![],
+                dimensions: dimensions.clone(),
+            }),
+        }
+    }
+
+    /// File name without `.svg` extension: the configured one, or one built from the parameters
+    pub fn filename(&self, container: &Container) -> String {
+        match &self.output.filename {
+            Some(name) => name.clone(),
+            None => format!(
+                "organizer_{}_rows_{}_columns_{}_thick_{}",
+                self.rows,
+                self.columns,
+                self.material_thickness,
+                container.key()
+            ),
+        }
+    }
+
+    /// Options for generating the SVG of the project
+    pub fn generation_options(&self) -> GenerationOptions {
+        let style = |color: &Color, layer: &String| OperationStyle {
+            color: color.to_string(),
+            layer: layer.clone(),
+        };
+
+        GenerationOptions {
+            operations: OperationStyles {
+                engrave_fill: style(&self.colors.engrave_fill, &self.layers.engrave_fill),
+                engrave_text: style(&self.colors.engrave_text, &self.layers.engrave_text),
+                score: style(&self.colors.score, &self.layers.score),
+                cut_inner: style(&self.colors.primary, &self.layers.cut_inner),
+                cut_outer: style(&self.colors.secondary, &self.layers.cut_outer),
+            },
+            unit: self.output.document_unit(),
+            common_line: self.cutting.common_line,
+            micro_tabs: self.cutting.micro_tabs.clone(),
+            profile: self.output.profile,
+            engrave_labels: self.output.engrave_labels,
+        }
+    }
+
+    /// Generate SVG for the project
+    pub fn generate(&self) -> Result<GeneratedSvg, ProjectError> {
+        self.validate()?;
+        self.generate_for(&self.resolve_container()?)
+    }
+
+    /// Generate SVG for the project with an already resolved container
+    pub fn generate_for(&self, container: &Container) -> Result<GeneratedSvg, ProjectError> {
+        self.validate()?;
+        self.check_micro_tabs_fit(container)?;
+
+        Ok(generate_svg_with_options(
+            self.rows,
+            self.columns,
+            self.material_thickness.to_mm(),
+            container,
+            &self.generation_options(),
+        ))
+    }
+
+    /// Check that every selected part with room for micro-tabs has room for all of them, by
+    /// generating the parts without tabs first
+    fn check_micro_tabs_fit(&self, container: &Container) -> Result<(), ProjectError> {
+        let options = self.generation_options();
+        let Some(micro_tabs) = options.micro_tabs.as_ref().filter(|tabs| tabs.count > 0) else {
+            return Ok(());
+        };
+        let without_tabs = GenerationOptions {
+            micro_tabs: None,
+            ..options.clone()
+        };
+        let generated = generate_svg_with_options(
+            self.rows,
+            self.columns,
+            self.material_thickness.to_mm(),
+            container,
+            &without_tabs,
+        );
+
+        let crowded = generated
+            .parts
+            .iter()
+            .filter(|part| micro_tabs.parts.contains(&part.kind))
+            .map(|part| (part, micro_tab_capacity(part, micro_tabs.width)))
+            .filter(|(_, capacity)| *capacity > 0 && *capacity < micro_tabs.count)
+            .min_by_key(|(_, capacity)| *capacity);
+
+        match crowded {
+            Some((part, capacity)) => Err(ProjectError::Invalid(vec![format!(
+                "{} has room for at most {} micro-tabs of {}mm, got {}",
+                part.label(),
+                capacity,
+                micro_tabs.width,
+                micro_tabs.count
+            )])),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Name of a file in the current directory: not empty, no path separators and no `.` or `..`
+fn is_plain_filename(name: &str) -> bool {
+    !name.is_empty() && name != "." && name != ".." && !name.contains(['/', '\\'])
+}
+
+fn is_json(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("json"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalogue::{builtin_containers, find_container_in};
+
+    /// Built-in container given inline, so the user catalogue doesn't change the rack
+    fn builtin(key: &str) -> ProjectContainer {
+        ProjectContainer::Inline(find_container_in(builtin_containers(), key).unwrap())
+    }
+
+    fn project() -> RackProject {
+        let mut project = RackProject::new(8, 3, Length::inches(0.25), builtin("smartstore-classic_1"));
+        project.output = OutputSettings {
+            unit: Some(Unit::Inch),
+            filename: Some("rack".to_string()),
+            profile: LaserSoftware::Epilog,
+            engrave_labels: true,
+        };
+        project
+    }
+
+    #[test]
+    fn test_toml_round_trip() {
+        let mut project = project();
+
+        let toml = project.to_toml().unwrap();
+
+        assert!(toml.contains("material_thickness = \"0.25in\""));
+        assert!(toml.contains("model = \"Classic 1\""));
+        assert_eq!(RackProject::from_toml(&toml).unwrap(), project);
+
+        project.container = ProjectContainer::Key("smartstore-classic_1".to_string());
+        let toml = project.to_toml().unwrap();
+        assert!(toml.contains("container = \"smartstore-classic_1\""));
+        assert_eq!(RackProject::from_toml(&toml).unwrap(), project);
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let project = project();
+
+        let json = project.to_json().unwrap();
+
+        assert_eq!(RackProject::from_json(&json).unwrap(), project);
+    }
+
+    #[test]
+    fn test_minimal_toml_uses_defaults() {
+        let project = RackProject::from_toml(
+            r#"
+            rows = 2
+            columns = 1
+            material_thickness = 4
+            container = "classic 1"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(project.material_thickness, Length::millimeters(4.0));
+        assert_eq!(project.colors, ProjectColors::default());
+        assert_eq!(project.layers, ProjectLayers::default());
+        assert_eq!(project.generation_options(), GenerationOptions::default());
+        assert_eq!(project.output, OutputSettings::default());
+        assert_eq!(project.container, ProjectContainer::Key("classic 1".to_string()));
+    }
+
+    #[test]
+    fn test_profile_unit_is_used_when_unit_is_not_set() {
+        let mut project = RackProject::from_toml(
+            r#"
+            rows = 2
+            columns = 1
+            material_thickness = 4
+            container = "classic 1"
+            output = { profile = "epilog" }
+            "#,
+        )
+        .unwrap();
+        project.container = builtin("smartstore-classic_1");
+
+        assert_eq!(project.output.unit, None);
+        assert_eq!(project.generation_options().unit, Unit::Inch);
+        let document = project.generate().unwrap().document.to_string();
+        assert!(document.contains("in\""));
+        assert!(!project.to_toml().unwrap().contains("unit"));
+
+        let mut millimeters = project.clone();
+        millimeters.output.unit = Some(Unit::Millimeter);
+        assert_eq!(millimeters.generation_options().unit, Unit::Millimeter);
+    }
+
+    #[test]
+    fn test_rack_parameters_are_validated() {
+        let content = |rows: usize, columns: usize, thickness: &str| {
+            format!(
+                "rows = {}\ncolumns = {}\nmaterial_thickness = {}\ncontainer = \"classic 1\"\n",
+                rows, columns, thickness
+            )
+        };
+
+        assert!(RackProject::from_toml(&content(1, 1, "4")).is_ok());
+        let error = RackProject::from_toml(&content(0, 0, "0")).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Invalid project: Rows must be at least 1, Columns must be at least 1, Material thickness must be positive"
+        );
+        let json = r#"{"rows": 2, "columns": 0, "material_thickness": 4, "container": "classic 1"}"#;
+        assert!(matches!(RackProject::from_json(json), Err(ProjectError::Invalid(_))));
+
+        let mut project = project();
+        project.columns = 0;
+        assert!(matches!(project.generate(), Err(ProjectError::Invalid(_))));
+    }
+
+    #[test]
+    fn test_filename_must_not_contain_directories() {
+        let mut project = project();
+        for filename in ["../rack", "/tmp/rack", "racks\\rack", "..", ""] {
+            project.output.filename = Some(filename.to_string());
+            assert!(matches!(project.validate(), Err(ProjectError::Invalid(_))), "{}", filename);
+        }
+
+        project.output.filename = Some("rack.v2".to_string());
+        assert!(project.validate().is_ok());
+    }
+
+    #[test]
+    fn test_micro_tabs_must_fit() {
+        let mut project = project();
+        project.cutting.micro_tabs = Some(MicroTabs {
+            count: 2000,
+            ..MicroTabs::default()
+        });
+
+        let error = project.generate().unwrap_err();
+        assert!(matches!(error, ProjectError::Invalid(_)));
+        assert!(error.to_string().contains("has room for at most"));
+
+        project.cutting.micro_tabs = Some(MicroTabs::default());
+        assert!(project.generate().is_ok());
+
+        let content = "rows = 1\ncolumns = 1\nmaterial_thickness = 4\ncontainer = \"classic 1\"\n\n[cutting.micro_tabs]\nwidth = 0\n";
+        assert!(matches!(RackProject::from_toml(content), Err(ProjectError::Invalid(_))));
+    }
+
+    #[test]
+    fn test_colors_are_validated_and_normalised() {
+        let content = |color: &str| {
+            format!(
+                "rows = 1\ncolumns = 1\nmaterial_thickness = 4\ncontainer = \"classic 1\"\n\n[colors]\nprimary = \"{}\"\n",
+                color
+            )
+        };
+
+        let project = RackProject::from_toml(&content("rgb(0, 0, 160)")).unwrap();
+        assert_eq!(project.colors.primary, Color::Rgb(0, 0, 160));
+        assert!(project.to_toml().unwrap().contains("primary = \"#0000A0\""));
+
+        let project = RackProject::from_toml(&content("Black")).unwrap();
+        assert_eq!(project.colors.primary, Color::Named("black"));
+
+        let error = RackProject::from_toml(&content("blakc")).unwrap_err();
+        assert!(error.to_string().contains("Unknown color 'blakc'"));
+    }
+
+    #[test]
+    fn test_colors_from_styles() {
+        let colors = ProjectColors::from_styles(&OperationStyles::with_cut_colors("#00f", "red")).unwrap();
+
+        assert_eq!(colors.primary, Color::Rgb(0, 0, 255));
+        assert_eq!(colors.secondary, Color::Named("red"));
+        assert_eq!(colors.score, ProjectColors::default().score);
+
+        let styles = OperationStyles::with_cut_colors("#00f", "not a color");
+        assert!(ProjectColors::from_styles(&styles).is_err());
+    }
+
+    #[test]
+    fn test_colors_from_palette() {
+        let colors = ProjectColors::from_palette(Palette::LightBurn);
+
+        assert_eq!(colors.primary.to_hex(), "#000000");
+        assert_eq!(colors.secondary.to_hex(), "#0000FF");
+        assert_eq!(colors.engrave_text.to_hex(), "#D0D000");
+    }
+
+    #[test]
+    fn test_inline_dimensions() {
+        let project = RackProject::from_toml(
+            r#"
+            rows = 2
+            columns = 1
+            material_thickness = "3mm"
+
+            [container]
+            width = 170.5
+            depth = 210
+            height = 56
+            side_wing_from_box_top = 15
+            side_wing_width = 7.6
+            "#,
+        )
+        .unwrap();
+
+        let container = project.resolve_container().unwrap();
+
+        assert_eq!(container.dimensions.width, 170.5);
+        assert_eq!(project.generate().unwrap().assembled_dimensions.height, 118.0);
+    }
+
+    #[test]
+    fn test_inline_dimensions_are_validated() {
+        let content = |depth: &str| {
+            format!(
+                "rows = 1\ncolumns = 1\nmaterial_thickness = 3\n\n[container]\nwidth = 170\ndepth = {}\nheight = 56\nside_wing_from_box_top = 15\nside_wing_width = 8\n",
+                depth
+            )
+        };
+
+        assert!(RackProject::from_toml(&content("210")).is_ok());
+        let error = RackProject::from_toml(&content("0")).unwrap_err();
+        assert!(matches!(error, ProjectError::Invalid(_)));
+        assert!(error.to_string().contains("Container: Depth must be at least"));
+
+        let mut project = project();
+        if let ProjectContainer::Inline(container) = &mut project.container {
+            container.dimensions.side_wing_width = container.dimensions.width;
+        }
+        assert!(matches!(project.generate(), Err(ProjectError::Invalid(_))));
+    }
+
+    #[test]
+    fn test_inline_container() {
+        let project = RackProject::from_json(
+            r#"{
+                "rows": 1,
+                "columns": 1,
+                "material_thickness": "4mm",
+                "container": {
+                    "vendor": "Acme",
+                    "model": "Crate",
+                    "dimensions": {
+                        "width": 170, "depth": 210, "height": 56,
+                        "side_wing_from_box_top": 15, "side_wing_width": 8
+                    }
+                }
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(project.resolve_container().unwrap().key(), "acme-crate");
+    }
+
+    #[test]
+    fn test_unknown_container() {
+        let mut project = project();
+        project.container = ProjectContainer::Key("wooden crate".to_string());
+
+        assert!(matches!(
+            project.generate(),
+            Err(ProjectError::Container(ContainerLookupError::NotFound { .. }))
+        ));
+    }
+
+    #[test]
+    fn test_regenerate_from_svg_metadata() {
+        let project = project();
+        let generated = project.generate().unwrap();
+
+        let inspected = crate::metadata::inspect(&generated.document.to_string()).unwrap();
+
+        assert_eq!(inspected.project.rows, 8);
+        assert_eq!(inspected.project.material_thickness.to_mm(), 6.35);
+        assert_eq!(inspected.project.output.unit, Some(Unit::Inch));
+        assert_eq!(
+            inspected.project.generate().unwrap().document.to_string(),
+            generated.document.to_string()
+        );
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let directory = std::env::temp_dir().join(format!("container-rack-project-{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        let project = project();
+
+        for file in ["rack.toml", "rack.json"] {
+            let path = directory.join(file);
+            project.save(&path).unwrap();
+            assert_eq!(RackProject::load(&path).unwrap(), project);
+        }
+        let json = fs::read_to_string(directory.join("rack.json")).unwrap();
+        fs::remove_dir_all(&directory).unwrap();
+
+        assert!(json.starts_with('{'));
+    }
+}
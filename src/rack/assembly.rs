@@ -0,0 +1,590 @@
+use std::fmt;
+
+use crate::rack::geometry::{Point, EPSILON};
+use crate::rack::{ContainerDimensions, Part, PartKind, CLEARANCE_FOR_CONTAINER_WIDTH};
+
+/// Direction in the assembled rack, seen from the front: `X` from left to right, `Y` from the
+/// top down and `Z` from the front to the back
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Axis {
+    X,
+    Y,
+    Z,
+}
+
+impl Axis {
+    fn index(&self) -> usize {
+        match self {
+            Axis::X => 0,
+            Axis::Y => 1,
+            Axis::Z => 2,
+        }
+    }
+}
+
+/// Axis aligned box in the assembled rack, in mm
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cuboid {
+    pub min: [f32; 3],
+    pub max: [f32; 3],
+}
+
+impl Cuboid {
+    fn intersection(&self, other: &Cuboid) -> Option<Cuboid> {
+        let mut intersection = *self;
+        for axis in 0..3 {
+            intersection.min[axis] = self.min[axis].max(other.min[axis]);
+            intersection.max[axis] = self.max[axis].min(other.max[axis]);
+            if intersection.max[axis] - intersection.min[axis] < EPSILON {
+                return None;
+            }
+        }
+        Some(intersection)
+    }
+
+    fn union(&self, other: &Cuboid) -> Cuboid {
+        let mut union = *self;
+        for axis in 0..3 {
+            union.min[axis] = self.min[axis].min(other.min[axis]);
+            union.max[axis] = self.max[axis].max(other.max[axis]);
+        }
+        union
+    }
+}
+
+impl fmt::Display for Cuboid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "x {:.1}..{:.1}, y {:.1}..{:.1}, z {:.1}..{:.1}",
+            self.min[0], self.max[0], self.min[1], self.max[1], self.min[2], self.max[2]
+        )
+    }
+}
+
+/// Part placed in the rack. Horizontal and vertical directions of the drawing and the
+/// thickness of the material run along the given axes, starting from the origin.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AssembledPart {
+    pub part: Part,
+    pub origin: [f32; 3],
+    /// Axes of the horizontal and vertical directions of the drawing and of the thickness
+    pub axes: [Axis; 3],
+    pub thickness: f32,
+}
+
+impl AssembledPart {
+    /// Position in the drawing and depth in the material of a point of the rack
+    fn to_drawing(&self, point: [f32; 3]) -> (Point, f32) {
+        let bounds = self.part.outline.bounds();
+        let local = |axis: Axis| point[axis.index()] - self.origin[axis.index()];
+
+        (
+            Point::new(bounds.x + local(self.axes[0]), bounds.y + local(self.axes[1])),
+            local(self.axes[2]),
+        )
+    }
+
+    /// Whether the point of the rack is in the material of the part
+    pub fn contains(&self, point: [f32; 3]) -> bool {
+        let (point, depth) = self.to_drawing(point);
+        depth > 0.0 && depth < self.thickness && self.part.contains(point)
+    }
+
+    pub fn bounds(&self) -> Cuboid {
+        let bounds = self.part.outline.bounds();
+        let mut cuboid = Cuboid {
+            min: self.origin,
+            max: self.origin,
+        };
+        for (axis, size) in self.axes.iter().zip([bounds.width, bounds.height, self.thickness]) {
+            cuboid.max[axis.index()] += size;
+        }
+        cuboid
+    }
+
+    /// Positions along the axis where the material of the part starts or ends
+    fn breakpoints(&self, axis: Axis) -> Vec<f32> {
+        let bounds = self.part.outline.bounds();
+        let origin = self.origin[axis.index()];
+        let polygons = || std::iter::once(&self.part.outline).chain(&self.part.holes);
+
+        if axis == self.axes[0] {
+            polygons()
+                .flat_map(|polygon| polygon.points.iter().map(|point| point.x))
+                .map(|x| origin + x - bounds.x)
+                .collect()
+        } else if axis == self.axes[1] {
+            polygons()
+                .flat_map(|polygon| polygon.points.iter().map(|point| point.y))
+                .map(|y| origin + y - bounds.y)
+                .collect()
+        } else {
+            vec![origin, origin + self.thickness]
+        }
+    }
+}
+
+/// Place where material of two parts is in the same place, e.g. a tab which doesn't meet its
+/// slot
+#[derive(Debug, Clone, PartialEq)]
+pub struct Interference {
+    pub part: String,
+    pub other: String,
+    /// Box around the material in the same place
+    pub region: Cuboid,
+}
+
+impl fmt::Display for Interference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} runs into {} at {}", self.part, self.other, self.region)
+    }
+}
+
+/// Tab of a part which doesn't go through a slot or notch of the part it joins, e.g. a tab
+/// which is too short or misses the other part
+#[derive(Debug, Clone, PartialEq)]
+pub struct LooseTab {
+    pub part: String,
+    pub other: String,
+    /// Box where the tab is
+    pub tab: Cuboid,
+}
+
+impl fmt::Display for LooseTab {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "tab of {} at {} doesn't go through a slot or notch of {}",
+            self.part, self.tab, self.other
+        )
+    }
+}
+
+/// Parts of a rack placed where they go when the rack is put together
+#[derive(Debug, Clone, PartialEq)]
+pub struct Assembly {
+    pub parts: Vec<AssembledPart>,
+    /// Parts whose tabs go into slots or notches of another part, as indices to `parts`
+    pub joints: Vec<(usize, usize)>,
+}
+
+impl Assembly {
+    /// Put generated parts together. Side panels stand a column apart with covers on top and
+    /// below them, and every box has a side wing on both side panels next to it: one of a
+    /// pair on the side panel to its left and the other on the side panel to its right.
+    pub fn new(
+        rows: usize,
+        columns: usize,
+        material_thickness: f32,
+        dimensions: &ContainerDimensions,
+        parts: &[Part],
+    ) -> Assembly {
+        let panel_x = |panel: usize| {
+            panel as f32 * (dimensions.width + CLEARANCE_FOR_CONTAINER_WIDTH + material_thickness)
+        };
+        let row_y = |row: usize| {
+            material_thickness + dimensions.side_wing_from_box_top + row as f32 * dimensions.height
+        };
+        let bottom_y = material_thickness + dimensions.height * rows as f32;
+
+        let placements = parts.iter().map(|part| match part.kind {
+            PartKind::Cover => {
+                let y = if part.number == 1 { 0.0 } else { bottom_y };
+                ([0.0, y, 0.0], [Axis::X, Axis::Z, Axis::Y])
+            }
+            PartKind::SidePanel => ([panel_x(part.number - 1), 0.0, 0.0], [Axis::Y, Axis::Z, Axis::X]),
+            PartKind::SideWing => {
+                let wing = part.number - 1;
+                let (row, column) = (wing / 2 / columns, wing / 2 % columns);
+                let x = if wing % 2 == 0 {
+                    // Tabs on the bottom edge of the drawing go into the side panel on the right
+                    panel_x(column + 1) - dimensions.side_wing_width
+                } else {
+                    panel_x(column)
+                };
+                ([x, row_y(row), 0.0], [Axis::Z, Axis::X, Axis::Y])
+            }
+        });
+
+        let index_of = |kind: PartKind, number: usize| {
+            parts
+                .iter()
+                .position(|part| part.kind == kind && part.number == number)
+        };
+        let joints = parts
+            .iter()
+            .enumerate()
+            .flat_map(|(index, part)| {
+                let others = match part.kind {
+                    PartKind::Cover => vec![],
+                    // Panels are numbered from 1, the one on the right of a column is column + 2
+                    PartKind::SideWing => {
+                        let wing = part.number - 1;
+                        let column = wing / 2 % columns;
+                        let panel = if wing % 2 == 0 { column + 2 } else { column + 1 };
+                        vec![index_of(PartKind::SidePanel, panel)]
+                    }
+                    PartKind::SidePanel => {
+                        vec![index_of(PartKind::Cover, 1), index_of(PartKind::Cover, 2)]
+                    }
+                };
+                others.into_iter().flatten().map(move |other| (index, other))
+            })
+            .collect();
+
+        Assembly {
+            parts: parts
+                .iter()
+                .zip(placements)
+                .map(|(part, (origin, axes))| AssembledPart {
+                    part: part.clone(),
+                    origin,
+                    axes,
+                    thickness: material_thickness,
+                })
+                .collect(),
+            joints,
+        }
+    }
+
+    /// Every pair of parts whose material is in the same place
+    pub fn interferences(&self) -> Vec<Interference> {
+        let mut interferences = vec![];
+
+        for (index, part) in self.parts.iter().enumerate() {
+            for other in &self.parts[index + 1..] {
+                if let Some(region) = interference(part, other) {
+                    interferences.push(Interference {
+                        part: part.part.id(),
+                        other: other.part.id(),
+                        region,
+                    });
+                }
+            }
+        }
+
+        interferences
+    }
+
+    /// Tabs of side wings and side panels which don't run through the whole thickness of the
+    /// part they join inside its outline, so they stop short of it, stick out of it or miss it
+    pub fn loose_tabs(&self) -> Vec<LooseTab> {
+        let mut loose_tabs = vec![];
+
+        for &(part, other) in &self.joints {
+            let (part, other) = (&self.parts[part], &self.parts[other]);
+            for tab in tabs(part, other) {
+                if !goes_through(&tab, part, other) {
+                    loose_tabs.push(LooseTab {
+                        part: part.part.id(),
+                        other: other.part.id(),
+                        tab,
+                    });
+                }
+            }
+        }
+
+        loose_tabs
+    }
+}
+
+fn sorted(mut values: Vec<f32>) -> Vec<f32> {
+    values.sort_by(f32::total_cmp);
+    values.dedup_by(|a, b| (*a - *b).abs() < EPSILON);
+    values
+}
+
+/// Tabs of the part towards the other part. Tabs are as long as the material is thick, so
+/// they are the material in the last material thickness of the part on the side of the
+/// other part.
+fn tabs(part: &AssembledPart, other: &AssembledPart) -> Vec<Cuboid> {
+    let along = other.axes[2];
+    let across = part.axes[..2]
+        .iter()
+        .copied()
+        .find(|axis| *axis != along)
+        .expect("tabs run along a direction of the drawing");
+    let (along, depth) = (along.index(), part.axes[2].index());
+    let bounds = part.bounds();
+    let other_bounds = other.bounds();
+
+    let towards_max =
+        other_bounds.min[along] + other_bounds.max[along] > bounds.min[along] + bounds.max[along];
+    let (from, to, probe) = if towards_max {
+        let from = bounds.max[along] - part.thickness;
+        (from, bounds.max[along], from + part.thickness / 4.0)
+    } else {
+        let to = bounds.min[along] + part.thickness;
+        (bounds.min[along], to, to - part.thickness / 4.0)
+    };
+
+    // Tabs are found close to where they leave the part, so short tabs are found too
+    let mut tabs: Vec<Cuboid> = vec![];
+    for pair in sorted(part.breakpoints(across)).windows(2) {
+        let mut point = [0.0; 3];
+        point[along] = probe;
+        point[across.index()] = (pair[0] + pair[1]) / 2.0;
+        point[depth] = (bounds.min[depth] + bounds.max[depth]) / 2.0;
+        if !part.contains(point) {
+            continue;
+        }
+
+        match tabs.last_mut() {
+            Some(tab) if (tab.max[across.index()] - pair[0]).abs() < EPSILON => {
+                tab.max[across.index()] = pair[1];
+            }
+            _ => {
+                let mut tab = bounds;
+                tab.min[along] = from;
+                tab.max[along] = to;
+                tab.min[across.index()] = pair[0];
+                tab.max[across.index()] = pair[1];
+                tabs.push(tab);
+            }
+        }
+    }
+
+    tabs
+}
+
+/// Whether the tab is within the outline of the other part and its material runs through
+/// the whole thickness of the other part
+fn goes_through(tab: &Cuboid, part: &AssembledPart, other: &AssembledPart) -> bool {
+    let along = other.axes[2].index();
+    let other_bounds = other.bounds();
+
+    let within = (0..3).filter(|axis| *axis != along).all(|axis| {
+        tab.min[axis] >= other_bounds.min[axis] - EPSILON
+            && tab.max[axis] <= other_bounds.max[axis] + EPSILON
+    });
+    if !within {
+        return false;
+    }
+
+    let (from, to) = (other_bounds.min[along], other_bounds.max[along]);
+    let values = sorted(
+        part.breakpoints(other.axes[2])
+            .into_iter()
+            .filter(|value| *value > from && *value < to)
+            .chain([from, to])
+            .collect(),
+    );
+    let mut point = [0, 1, 2].map(|axis| (tab.min[axis] + tab.max[axis]) / 2.0);
+    values.windows(2).all(|pair| {
+        point[along] = (pair[0] + pair[1]) / 2.0;
+        part.contains(point)
+    })
+}
+
+/// Box around the material of both parts in the same place. The space where the parts
+/// meet is split to cells at every corner of either part, so each cell is either material or
+/// not for both parts.
+fn interference(a: &AssembledPart, b: &AssembledPart) -> Option<Cuboid> {
+    let space = a.bounds().intersection(&b.bounds())?;
+    let [xs, ys, zs] = [Axis::X, Axis::Y, Axis::Z].map(|axis| {
+        let (min, max) = (space.min[axis.index()], space.max[axis.index()]);
+        let mut values: Vec<f32> = a
+            .breakpoints(axis)
+            .into_iter()
+            .chain(b.breakpoints(axis))
+            .filter(|value| *value > min && *value < max)
+            .chain([min, max])
+            .collect();
+        values.sort_by(f32::total_cmp);
+        values.dedup_by(|a, b| (*a - *b).abs() < EPSILON);
+        values
+    });
+
+    let mut region: Option<Cuboid> = None;
+    for x in xs.windows(2) {
+        for y in ys.windows(2) {
+            for z in zs.windows(2) {
+                let center = [(x[0] + x[1]) / 2.0, (y[0] + y[1]) / 2.0, (z[0] + z[1]) / 2.0];
+                if a.contains(center) && b.contains(center) {
+                    let cell = Cuboid {
+                        min: [x[0], y[0], z[0]],
+                        max: [x[1], y[1], z[1]],
+                    };
+                    region = Some(region.map_or(cell, |region| region.union(&cell)));
+                }
+            }
+        }
+    }
+
+    region
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalogue::{builtin_containers, find_container_in};
+    use crate::rack::{generate_svg_with_options, Container, GenerationOptions, MINIMUM_CONTAINER_DEPTH};
+    use proptest::prelude::*;
+
+    fn assemble(rows: usize, columns: usize) -> Assembly {
+        let container = find_container_in(builtin_containers(), "smartstore-classic_1").unwrap();
+        let generated =
+            generate_svg_with_options(rows, columns, 4.0, &container, &GenerationOptions::default());
+
+        Assembly::new(rows, columns, 4.0, &container.dimensions, &generated.parts)
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(50))]
+
+        #[test]
+        fn test_every_tab_meets_its_slot(
+            rows in 1usize..=4,
+            columns in 1usize..=4,
+            material_thickness in 2.0f32..=8.0,
+            width in 50.0f32..=300.0,
+            depth in MINIMUM_CONTAINER_DEPTH..=400.0,
+            height in 40.0f32..=200.0,
+            side_wing_from_box_top in 5.0f32..=30.0,
+            side_wing_width in 5.0f32..=20.0,
+            common_line in any::<bool>(),
+        ) {
+            let container = Container {
+                vendor: "Test".to_string(),
+                model: "Test".to_string(),
+                description: "Test".to_string(),
+                links: vec![],
+                dimensions: ContainerDimensions {
+                    width,
+                    depth,
+                    height,
+                    side_wing_from_box_top,
+                    side_wing_width,
+                },
+            };
+            let options = GenerationOptions {
+                common_line,
+                ..GenerationOptions::default()
+            };
+            let generated =
+                generate_svg_with_options(rows, columns, material_thickness, &container, &options);
+
+            let assembly =
+                Assembly::new(rows, columns, material_thickness, &container.dimensions, &generated.parts);
+
+            prop_assert_eq!(assembly.interferences(), vec![]);
+            prop_assert_eq!(assembly.loose_tabs(), vec![]);
+        }
+    }
+
+    #[test]
+    fn test_generated_rack_fits_together() {
+        let assembly = assemble(2, 3);
+
+        assert_eq!(assembly.interferences(), []);
+        assert_eq!(assembly.loose_tabs(), []);
+        // Two tabs of every side wing and two tabs into both covers of every side panel
+        let tabs: usize = assembly
+            .joints
+            .iter()
+            .map(|&(part, other)| tabs(&assembly.parts[part], &assembly.parts[other]).len())
+            .sum();
+        assert_eq!(tabs, 2 * 2 * 3 * 2 + 4 * 2 * 2);
+    }
+
+    #[test]
+    fn test_parts_meet_where_they_are_joined() {
+        let assembly = assemble(1, 2);
+        let bounds = |id: &str| {
+            assembly
+                .parts
+                .iter()
+                .find(|part| part.part.id() == id)
+                .unwrap()
+                .bounds()
+        };
+
+        // Tabs of the side panel go through the top cover
+        let top = bounds("cover-1");
+        let panel = bounds("side-panel-2");
+        assert!(top.intersection(&panel).is_some());
+        // Tabs of side wings go through the side panels next to them
+        assert!(bounds("side-wing-1").intersection(&bounds("side-panel-2")).is_some());
+        assert!(bounds("side-wing-2").intersection(&bounds("side-panel-1")).is_some());
+        assert!(bounds("side-wing-1").intersection(&bounds("side-panel-1")).is_none());
+    }
+
+    #[test]
+    fn test_misplaced_slot_is_reported() {
+        let mut assembly = assemble(1, 1);
+        let panel = assembly
+            .parts
+            .iter_mut()
+            .find(|part| part.part.id() == "side-panel-1")
+            .unwrap();
+        panel.part.holes = panel
+            .part
+            .holes
+            .iter()
+            .map(|hole| hole.translate(0.0, 5.0))
+            .collect();
+
+        let interferences = assembly.interferences();
+
+        assert_eq!(interferences.len(), 1);
+        assert_eq!(interferences[0].part, "side-wing-2");
+        assert_eq!(interferences[0].other, "side-panel-1");
+        // Front tab of the wing starts 55mm from the front, 5mm before its moved slot
+        let region = interferences[0].region;
+        assert!((region.min[2] - 55.0).abs() < 1e-3);
+        assert!((region.max[1] - region.min[1] - 4.0).abs() < 1e-3);
+    }
+
+    fn part_mut<'a>(assembly: &'a mut Assembly, id: &str) -> &'a mut Part {
+        &mut assembly
+            .parts
+            .iter_mut()
+            .find(|part| part.part.id() == id)
+            .unwrap()
+            .part
+    }
+
+    #[test]
+    fn test_short_tab_is_reported() {
+        let mut assembly = assemble(1, 1);
+        let wing = part_mut(&mut assembly, "side-wing-1");
+        let bounds = wing.outline.bounds();
+        // Front tab of the wing is 20mm from the front and reaches only halfway into the side panel
+        for point in wing.outline.points.iter_mut() {
+            if (point.y - (bounds.y + bounds.height)).abs() < EPSILON && point.x < bounds.x + 50.0 {
+                point.y -= 2.0;
+            }
+        }
+
+        let loose_tabs = assembly.loose_tabs();
+
+        assert_eq!(assembly.interferences(), []);
+        assert_eq!(loose_tabs.len(), 1);
+        assert_eq!(loose_tabs[0].part, "side-wing-1");
+        assert_eq!(loose_tabs[0].other, "side-panel-2");
+        assert!((loose_tabs[0].tab.min[2] - 20.0).abs() < 1e-3);
+        assert!((loose_tabs[0].tab.max[2] - 40.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_tab_outside_notch_is_reported() {
+        let mut assembly = assemble(1, 1);
+        let cover = part_mut(&mut assembly, "cover-1");
+        let right = cover.outline.bounds().x + cover.outline.bounds().width;
+        // Cover one material thickness narrower, so tabs of the last side panel miss its notches
+        for point in cover.outline.points.iter_mut() {
+            if point.x > right - 4.0 - EPSILON {
+                point.x -= 4.0;
+            }
+        }
+
+        let loose_tabs = assembly.loose_tabs();
+
+        assert_eq!(assembly.interferences(), []);
+        assert_eq!(loose_tabs.len(), 2);
+        assert!(loose_tabs
+            .iter()
+            .all(|tab| tab.part == "side-panel-2" && tab.other == "cover-1"));
+    }
+}
@@ -0,0 +1,195 @@
+use crate::rack::geometry::{join_segments, Point, EPSILON};
+use crate::rack::{Cut, Part};
+
+type Segment = (Point, Point);
+
+/// Leave out lines which an earlier cut has already cut, so edges shared by adjacent parts are
+/// cut only once. Lines of operations which don't cut through are kept as they are.
+///
+/// Only horizontal and vertical lines are compared. Cuts which lose some of their lines are
+/// split to open lines following the remaining edges, other cuts are kept as they are.
+pub fn share_common_lines(parts: &mut [Part]) {
+    let mut cut_lines: Vec<Segment> = vec![];
+
+    for part in parts.iter_mut() {
+        for cut in std::mem::take(&mut part.cuts) {
+            // Scores and engravings don't cut, so they neither share nor replace cuts
+            if !cut.operation.is_cut() {
+                part.cuts.push(cut);
+                continue;
+            }
+            let segments = cut.path.segments();
+            let remaining: Vec<Vec<Segment>> = segments
+                .iter()
+                .map(|segment| uncut_pieces(*segment, &cut_lines))
+                .collect();
+            let unchanged = remaining
+                .iter()
+                .zip(&segments)
+                .all(|(pieces, segment)| pieces.len() == 1 && pieces[0] == *segment);
+            cut_lines.extend(segments);
+
+            if unchanged {
+                part.cuts.push(cut);
+            } else {
+                let pieces = remaining.into_iter().flatten().collect();
+                part.cuts.extend(join_segments(pieces, cut.path.closed).into_iter().map(|path| Cut {
+                    operation: cut.operation,
+                    path,
+                }));
+            }
+        }
+    }
+}
+
+fn is_vertical((start, end): Segment) -> bool {
+    (start.x - end.x).abs() < EPSILON
+}
+
+fn is_horizontal((start, end): Segment) -> bool {
+    (start.y - end.y).abs() < EPSILON
+}
+
+/// Parts of the segment no earlier cut runs along, in the direction of the segment
+pub(crate) fn uncut_pieces(segment: Segment, cut_lines: &[Segment]) -> Vec<Segment> {
+    let vertical = is_vertical(segment);
+    if !vertical && !is_horizontal(segment) {
+        return vec![segment];
+    }
+
+    // Position along the line and position of the line itself
+    let along = |point: Point| if vertical { point.y } else { point.x };
+    let across = |point: Point| if vertical { point.x } else { point.y };
+    let point_at = |position: f32| {
+        if vertical {
+            Point::new(segment.0.x, position)
+        } else {
+            Point::new(position, segment.0.y)
+        }
+    };
+
+    let mut covered: Vec<(f32, f32)> = cut_lines
+        .iter()
+        .filter(|line| {
+            let parallel = if vertical {
+                is_vertical(**line)
+            } else {
+                is_horizontal(**line)
+            };
+            parallel && (across(line.0) - across(segment.0)).abs() < EPSILON
+        })
+        .map(|(start, end)| {
+            let (a, b) = (along(*start), along(*end));
+            (a.min(b), a.max(b))
+        })
+        .collect();
+    covered.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let (from, to) = (along(segment.0), along(segment.1));
+    let last = from.max(to);
+    let mut position = from.min(to);
+    let mut pieces = vec![];
+    for (start, end) in covered {
+        if start >= last {
+            break;
+        }
+        if start > position + EPSILON {
+            pieces.push((position, start));
+        }
+        position = position.max(end);
+    }
+    if last > position + EPSILON {
+        pieces.push((position, last));
+    }
+
+    if from <= to {
+        pieces
+            .into_iter()
+            .map(|(a, b)| (point_at(a), point_at(b)))
+            .collect()
+    } else {
+        pieces
+            .into_iter()
+            .rev()
+            .map(|(a, b)| (point_at(b), point_at(a)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rack::geometry::{Polygon, Polyline};
+    use crate::rack::operations::Operation;
+    use crate::rack::PartKind;
+
+    fn square(x: f32, y: f32, size: f32) -> Polygon {
+        Polygon::starting_at(x, y)
+            .vertical_line_to(y + size)
+            .horizontal_line_to(x + size)
+            .vertical_line_to(y)
+    }
+
+    fn cut_length(parts: &[Part]) -> f32 {
+        parts
+            .iter()
+            .flat_map(|part| &part.cuts)
+            .map(|cut| cut.path.length())
+            .sum()
+    }
+
+    #[test]
+    fn test_shared_edge_is_cut_once() {
+        let mut parts = [
+            Part::new(PartKind::Cover, square(0.0, 0.0, 10.0), vec![]),
+            Part::new(PartKind::Cover, square(0.0, 10.0, 10.0), vec![]),
+        ];
+
+        share_common_lines(&mut parts);
+
+        assert_eq!(parts[0].cuts[0].path, Polyline::from(&parts[0].outline));
+        assert_eq!(parts[1].cuts.len(), 1);
+        assert_eq!(parts[1].cuts[0].operation, Operation::CutOuter);
+        assert_eq!(
+            parts[1].cuts[0].path,
+            Polyline {
+                points: vec![
+                    Point::new(0.0, 10.0),
+                    Point::new(0.0, 20.0),
+                    Point::new(10.0, 20.0),
+                    Point::new(10.0, 10.0),
+                ],
+                closed: false,
+            }
+        );
+        assert_eq!(cut_length(&parts), 70.0);
+    }
+
+    #[test]
+    fn test_partly_shared_edge() {
+        let mut parts = [
+            Part::new(PartKind::Cover, square(0.0, 0.0, 10.0), vec![]),
+            Part::new(PartKind::Cover, square(5.0, 10.0, 10.0), vec![]),
+        ];
+
+        share_common_lines(&mut parts);
+
+        assert_eq!(cut_length(&parts), 75.0);
+        assert_eq!(parts[1].cuts.len(), 1);
+        assert_eq!(parts[1].cuts[0].path.start(), Point::new(5.0, 10.0));
+        assert_eq!(parts[1].cuts[0].path.end(), Point::new(10.0, 10.0));
+    }
+
+    #[test]
+    fn test_separate_parts_are_unchanged() {
+        let mut parts = [
+            Part::new(PartKind::Cover, square(0.0, 0.0, 10.0), vec![]),
+            Part::new(PartKind::Cover, square(0.0, 13.0, 10.0), vec![]),
+        ];
+        let original = parts.clone();
+
+        share_common_lines(&mut parts);
+
+        assert_eq!(parts, original);
+    }
+}
@@ -0,0 +1,310 @@
+use svg::node::element::path::Data;
+
+/// Points closer than this are the same point, in mm
+pub(crate) const EPSILON: f32 = 1e-3;
+
+/// Point on the drawing, in mm
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Point {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl Point {
+    pub fn new(x: f32, y: f32) -> Self {
+        Point { x, y }
+    }
+}
+
+/// Axis aligned rectangle, in mm
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rect {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Rect {
+    pub fn area(&self) -> f32 {
+        self.width * self.height
+    }
+
+    /// Smallest rectangle containing both rectangles
+    pub fn union(&self, other: &Rect) -> Rect {
+        let x = self.x.min(other.x);
+        let y = self.y.min(other.y);
+
+        Rect {
+            x,
+            y,
+            width: (self.x + self.width).max(other.x + other.width) - x,
+            height: (self.y + self.height).max(other.y + other.height) - y,
+        }
+    }
+}
+
+/// Closed outline made of straight lines. The last point connects back to the first one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polygon {
+    pub points: Vec<Point>,
+}
+
+impl Polygon {
+    pub fn starting_at(x: f32, y: f32) -> Self {
+        Polygon {
+            points: vec![Point::new(x, y)],
+        }
+    }
+
+    fn last(&self) -> Point {
+        *self.points.last().expect("polygon has a starting point")
+    }
+
+    /// Add vertical line from the last point to `y`
+    pub fn vertical_line_to(mut self, y: f32) -> Self {
+        let x = self.last().x;
+        self.points.push(Point::new(x, y));
+        self
+    }
+
+    /// Add horizontal line from the last point to `x`
+    pub fn horizontal_line_to(mut self, x: f32) -> Self {
+        let y = self.last().y;
+        self.points.push(Point::new(x, y));
+        self
+    }
+
+    /// Polygon moved by given offset
+    pub fn translate(&self, dx: f32, dy: f32) -> Polygon {
+        Polygon {
+            points: self
+                .points
+                .iter()
+                .map(|point| Point::new(point.x + dx, point.y + dy))
+                .collect(),
+        }
+    }
+
+    /// Lines of the polygon, including the closing line
+    pub fn segments(&self) -> impl Iterator<Item = (Point, Point)> + '_ {
+        self.points
+            .iter()
+            .zip(self.points.iter().cycle().skip(1))
+            .map(|(start, end)| (*start, *end))
+    }
+
+    /// Length of the outline, including the closing line
+    pub fn perimeter(&self) -> f32 {
+        self.segments()
+            .map(|(start, end)| (end.x - start.x).hypot(end.y - start.y))
+            .sum()
+    }
+
+    /// Enclosed area
+    pub fn area(&self) -> f32 {
+        let twice_area: f32 = self
+            .segments()
+            .map(|(start, end)| start.x * end.y - end.x * start.y)
+            .sum();
+
+        twice_area.abs() / 2.0
+    }
+
+    pub fn bounds(&self) -> Rect {
+        let min_x = self.points.iter().map(|p| p.x).fold(f32::INFINITY, f32::min);
+        let min_y = self.points.iter().map(|p| p.y).fold(f32::INFINITY, f32::min);
+        let max_x = self.points.iter().map(|p| p.x).fold(f32::NEG_INFINITY, f32::max);
+        let max_y = self.points.iter().map(|p| p.y).fold(f32::NEG_INFINITY, f32::max);
+
+        Rect {
+            x: min_x,
+            y: min_y,
+            width: max_x - min_x,
+            height: max_y - min_y,
+        }
+    }
+
+    /// Whether the point is inside the polygon. Points on the outline may be either.
+    pub fn contains(&self, point: Point) -> bool {
+        self.segments()
+            .filter(|(start, end)| (start.y > point.y) != (end.y > point.y))
+            .filter(|(start, end)| {
+                let x = start.x + (point.y - start.y) / (end.y - start.y) * (end.x - start.x);
+                point.x < x
+            })
+            .count()
+            % 2
+            == 1
+    }
+
+    /// SVG path data using horizontal and vertical commands where possible
+    pub fn to_data(&self) -> Data {
+        path_data(&self.points, true)
+    }
+}
+
+/// Line through points, closed back to the first point or left open
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polyline {
+    pub points: Vec<Point>,
+    pub closed: bool,
+}
+
+impl Polyline {
+    /// Where cutting starts
+    pub fn start(&self) -> Point {
+        self.points[0]
+    }
+
+    /// Where cutting ends, closed lines end where they start
+    pub fn end(&self) -> Point {
+        if self.closed {
+            self.start()
+        } else {
+            *self.points.last().expect("polyline has points")
+        }
+    }
+
+    /// Straight lines from point to point, including the closing line of a closed line
+    pub fn segments(&self) -> Vec<(Point, Point)> {
+        let closing_point = self.closed.then(|| self.start());
+        let points: Vec<Point> = self.points.iter().copied().chain(closing_point).collect();
+
+        points.windows(2).map(|pair| (pair[0], pair[1])).collect()
+    }
+
+    pub fn length(&self) -> f32 {
+        self.segments()
+            .iter()
+            .map(|(start, end)| (end.x - start.x).hypot(end.y - start.y))
+            .sum()
+    }
+
+    /// SVG path data using horizontal and vertical commands where possible
+    pub fn to_data(&self) -> Data {
+        path_data(&self.points, self.closed)
+    }
+}
+
+impl From<&Polygon> for Polyline {
+    fn from(polygon: &Polygon) -> Self {
+        Polyline {
+            points: polygon.points.clone(),
+            closed: true,
+        }
+    }
+}
+
+fn connects(a: Point, b: Point) -> bool {
+    (a.x - b.x).abs() < EPSILON && (a.y - b.y).abs() < EPSILON
+}
+
+/// Join consecutive segments left of a line to open lines. Segments of a closed line are
+/// rotated to start after a gap, so no line is split at the starting point.
+pub(crate) fn join_segments(mut segments: Vec<(Point, Point)>, closed: bool) -> Vec<Polyline> {
+    if closed {
+        if let Some(first) = (0..segments.len()).find(|&index| {
+            let previous = segments[(index + segments.len() - 1) % segments.len()];
+            !connects(previous.1, segments[index].0)
+        }) {
+            segments.rotate_left(first);
+        }
+    }
+
+    let mut lines: Vec<Polyline> = vec![];
+    for (start, end) in segments {
+        match lines.last_mut() {
+            Some(line) if connects(line.end(), start) => line.points.push(end),
+            _ => lines.push(Polyline {
+                points: vec![start, end],
+                closed: false,
+            }),
+        }
+    }
+
+    lines
+}
+
+fn path_data(points: &[Point], closed: bool) -> Data {
+    let first = points[0];
+    let mut data = Data::new().move_to((first.x, first.y));
+
+    for (previous, point) in points.iter().zip(&points[1..]) {
+        data = if point.x == previous.x {
+            data.vertical_line_to(point.y)
+        } else if point.y == previous.y {
+            data.horizontal_line_to(point.x)
+        } else {
+            data.line_to((point.x, point.y))
+        };
+    }
+
+    if closed {
+        data.close()
+    } else {
+        data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square_with_notch() -> Polygon {
+        Polygon::starting_at(0.0, 0.0)
+            .vertical_line_to(10.0)
+            .horizontal_line_to(4.0)
+            .vertical_line_to(8.0)
+            .horizontal_line_to(6.0)
+            .vertical_line_to(10.0)
+            .horizontal_line_to(10.0)
+            .vertical_line_to(0.0)
+    }
+
+    #[test]
+    fn test_measurements() {
+        let polygon = square_with_notch();
+
+        assert_eq!(polygon.area(), 96.0);
+        assert!(polygon.contains(Point::new(5.0, 5.0)));
+        assert!(!polygon.contains(Point::new(5.0, 9.0)));
+        assert!(!polygon.contains(Point::new(11.0, 5.0)));
+        assert_eq!(polygon.perimeter(), 44.0);
+        assert_eq!(
+            polygon.bounds(),
+            Rect {
+                x: 0.0,
+                y: 0.0,
+                width: 10.0,
+                height: 10.0
+            }
+        );
+    }
+
+    #[test]
+    fn test_open_polyline() {
+        let polyline = Polyline {
+            points: vec![Point::new(0.0, 0.0), Point::new(0.0, 10.0), Point::new(4.0, 10.0)],
+            closed: false,
+        };
+
+        assert_eq!(polyline.length(), 14.0);
+        assert_eq!(polyline.end(), Point::new(4.0, 10.0));
+        assert_eq!(
+            svg::node::Value::from(polyline.to_data()).to_string(),
+            "M0,0 V10 H4"
+        );
+        assert_eq!(Polyline::from(&square_with_notch()).length(), 44.0);
+    }
+
+    #[test]
+    fn test_path_data() {
+        let data = square_with_notch().translate(5.0, 0.5).to_data();
+
+        assert_eq!(
+            svg::node::Value::from(data).to_string(),
+            "M5,0.5 V10.5 H9 V8.5 H11 V10.5 H15 V0.5 z"
+        );
+    }
+}
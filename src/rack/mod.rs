@@ -1,22 +1,61 @@
-use svg::node::element::path::Data;
-use svg::node::element::Path;
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use svg::node::element::{Group, Path};
+use serde::{Deserialize, Serialize};
 use svg::{Document, Node};
 use url::Url;
 
-// All measurements are in mm
-const SIDE_WING_SLOT_FROM_FRONT: usize = 20;
-const SIDE_WING_SLOT_WIDTH: usize = 20;
-const SIDE_WING_SLOT_SPACING: usize = 15;
-const CLEARANCE_BETWEEN_PATHS: usize = 3;
-const SIDE_TAP_FROM_FRONT: usize = 30;
-const SIDE_TAP_WIDTH: usize = 30;
-const CLEARANCE_FOR_CONTAINER_WIDTH: usize = 4;
+pub mod assembly;
+pub mod common_line;
+pub mod geometry;
+pub mod operations;
+pub mod ordering;
+pub mod output;
+pub mod stats;
+pub mod tabs;
+
+use crate::metadata::DesignMetadata;
+use common_line::share_common_lines;
+use geometry::{Point, Polygon, Polyline};
+use operations::{Operation, OperationStyles};
+use ordering::{cutting_order, order_for_cutting};
+use output::{LaserSoftware, OutputProfile};
+use stats::LayoutStats;
+use tabs::{add_micro_tabs, MicroTabs};
+use crate::project::{
+    CuttingSettings, OutputSettings, ProjectColors, ProjectContainer, ProjectLayers, RackProject,
+};
+use crate::units::{Length, Unit};
 
-#[derive(Debug, Clone)]
+// All measurements are in mm
+const SIDE_WING_SLOT_FROM_FRONT: f32 = 20.0;
+const SIDE_WING_SLOT_WIDTH: f32 = 20.0;
+const SIDE_WING_SLOT_SPACING: f32 = 15.0;
+const CLEARANCE_BETWEEN_PATHS: f32 = 3.0;
+const SIDE_TAP_FROM_FRONT: f32 = 30.0;
+const SIDE_TAP_WIDTH: f32 = 30.0;
+const CLEARANCE_FOR_CONTAINER_WIDTH: f32 = 4.0;
+const PROFILE_TOLERANCE: f32 = 0.5;
+const INKSCAPE_NAMESPACE: &str = "http://www.inkscape.org/namespaces/inkscape";
+/// Height of engraved part labels, smaller on narrow parts
+const LABEL_SIZE: f32 = 5.0;
+
+/// Shortest container which has room for all four side wing slots
+pub const MINIMUM_CONTAINER_DEPTH: f32 = 2.0
+    * (SIDE_WING_SLOT_FROM_FRONT
+        + SIDE_WING_SLOT_WIDTH
+        + SIDE_WING_SLOT_SPACING
+        + SIDE_WING_SLOT_WIDTH);
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Container {
     pub vendor: String,
     pub model: String,
+    #[serde(default)]
     pub description: String,
+    #[serde(default)]
     pub links: Vec<ContainerLink>,
     pub dimensions: ContainerDimensions,
 }
@@ -27,18 +66,95 @@ impl Container {
             .to_lowercase()
             .replace(" ", "_")
     }
+
+    /// Whether this container can be used in a rack generated for `rack_container`
+    pub fn fits_rack_for(&self, rack_container: &Container) -> bool {
+        self.dimensions
+            .profile()
+            .matches(&rack_container.dimensions.profile())
+    }
+
+    /// How many rows this container takes in a rack generated for `rack_container`
+    pub fn rows_needed_in_rack_for(&self, rack_container: &Container) -> usize {
+        (self.dimensions.height / rack_container.dimensions.height).ceil() as usize
+    }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ContainerDimensions {
-    pub width: usize,
-    pub depth: usize,
-    pub height: usize,
-    pub side_wing_from_box_top: usize,
-    pub side_wing_width: usize,
+    pub width: f32,
+    pub depth: f32,
+    pub height: f32,
+    pub side_wing_from_box_top: f32,
+    pub side_wing_width: f32,
 }
 
-#[derive(Debug, Clone)]
+impl ContainerDimensions {
+    /// Check that a rack can be generated for a container with these dimensions
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let mut problems = Vec::new();
+
+        if self.width <= 0.0 {
+            problems.push("Width must be positive".to_string());
+        }
+        if self.height <= 0.0 {
+            problems.push("Height must be positive".to_string());
+        }
+        if self.depth < MINIMUM_CONTAINER_DEPTH {
+            problems.push(format!(
+                "Depth must be at least {}mm to fit the side wing slots",
+                MINIMUM_CONTAINER_DEPTH
+            ));
+        }
+        if self.side_wing_from_box_top <= 0.0 || self.side_wing_from_box_top >= self.height {
+            problems.push("Side wing must be between top and bottom of the box".to_string());
+        }
+        if self.side_wing_width <= 0.0 || self.side_wing_width * 2.0 >= self.width {
+            problems.push("Side wing width must be positive and less than half of box width".to_string());
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(problems)
+        }
+    }
+
+    pub fn profile(&self) -> ContainerProfile {
+        ContainerProfile {
+            width: self.width,
+            depth: self.depth,
+            side_wing_from_box_top: self.side_wing_from_box_top,
+            side_wing_width: self.side_wing_width,
+        }
+    }
+}
+
+/// Geometry that decides whether a container fits a rack. Containers sharing a profile
+/// form a family, only their height differs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContainerProfile {
+    pub width: f32,
+    pub depth: f32,
+    pub side_wing_from_box_top: f32,
+    pub side_wing_width: f32,
+}
+
+impl ContainerProfile {
+    /// Profiles match when they differ less than what a rack can tolerate
+    pub fn matches(&self, other: &ContainerProfile) -> bool {
+        [
+            (self.width, other.width),
+            (self.depth, other.depth),
+            (self.side_wing_from_box_top, other.side_wing_from_box_top),
+            (self.side_wing_width, other.side_wing_width),
+        ]
+        .iter()
+        .all(|(a, b)| (a - b).abs() <= PROFILE_TOLERANCE)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ContainerLink {
     pub url: Url,
     pub title: String,
@@ -49,11 +165,291 @@ pub struct AssembledDimensions {
     pub height: f32,
     pub depth: f32
 }
+
+impl AssembledDimensions {
+    /// Dimensions converted from millimeters to given unit
+    pub fn to_unit(&self, unit: Unit) -> AssembledDimensions {
+        AssembledDimensions {
+            width: unit.from_mm(self.width),
+            height: unit.from_mm(self.height),
+            depth: unit.from_mm(self.depth),
+        }
+    }
+}
+/// Size of a material sheet, in mm. Used for the sheet all parts of a rack are laid out on
+/// and for stock sheets.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SheetDimensions {
+    pub width: f32,
+    pub height: f32,
+}
+
+impl SheetDimensions {
+    /// Area of the sheet in mm²
+    pub fn area(&self) -> f32 {
+        self.width * self.height
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct GeneratedSvg {
     pub document: Document,
     pub assembled_dimensions: AssembledDimensions,
+    pub sheet_dimensions: SheetDimensions,
+    /// Parts in the order they are generated, see [`ordering::cutting_order`] for the order
+    /// they are cut
+    pub parts: Vec<Part>,
+    pub stats: LayoutStats,
+}
+/// Kinds of parts a rack is cut from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PartKind {
+    SideWing,
+    /// Top or bottom piece
+    Cover,
+    SidePanel,
+}
+
+impl fmt::Display for PartKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PartKind::SideWing => write!(f, "side wing"),
+            PartKind::Cover => write!(f, "cover"),
+            PartKind::SidePanel => write!(f, "side panel"),
+        }
+    }
+}
+
+impl PartKind {
+    /// Identifier of the kind, e.g. `side-wing`
+    pub fn id(&self) -> &'static str {
+        match self {
+            PartKind::SideWing => "side-wing",
+            PartKind::Cover => "cover",
+            PartKind::SidePanel => "side-panel",
+        }
+    }
+}
+
+impl FromStr for PartKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().replace(['-', ' '], "_").as_str() {
+            "side_wing" => Ok(PartKind::SideWing),
+            "cover" => Ok(PartKind::Cover),
+            "side_panel" => Ok(PartKind::SidePanel),
+            other => Err(format!(
+                "Unknown part '{}', expected 'side-wing', 'cover' or 'side-panel'",
+                other
+            )),
+        }
+    }
+}
+
+/// Line the laser follows
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cut {
+    pub operation: Operation,
+    pub path: Polyline,
 }
+
+/// Text engraved on a part, centred on its position
+#[derive(Debug, Clone, PartialEq)]
+pub struct Text {
+    pub text: String,
+    pub position: Point,
+    /// Height of the letters in mm
+    pub size: f32,
+}
+
+/// Part as drawn on the SVG: holes are cut before the outline
+#[derive(Debug, Clone, PartialEq)]
+pub struct Part {
+    pub kind: PartKind,
+    /// Number of the part among parts of its kind, from 1 in the order they are generated
+    pub number: usize,
+    pub outline: Polygon,
+    pub holes: Vec<Polygon>,
+    /// Lines of every operation in the order they are run: engravings and scores first,
+    /// then holes and the outline last. Without common line cutting holes and the outline
+    /// are closed lines.
+    pub cuts: Vec<Cut>,
+    /// Text engraved with [`Operation::EngraveText`], e.g. the label of the part
+    pub texts: Vec<Text>,
+}
+
+impl Part {
+    pub fn new(kind: PartKind, outline: Polygon, holes: Vec<Polygon>) -> Part {
+        let cuts = holes
+            .iter()
+            .map(|hole| Cut {
+                operation: Operation::CutInner,
+                path: Polyline::from(hole),
+            })
+            .chain(std::iter::once(Cut {
+                operation: Operation::CutOuter,
+                path: Polyline::from(&outline),
+            }))
+            .collect();
+
+        Part {
+            kind,
+            number: 1,
+            outline,
+            holes,
+            cuts,
+            texts: vec![],
+        }
+    }
+
+    /// Same part with given number
+    pub fn numbered(self, number: usize) -> Part {
+        Part { number, ..self }
+    }
+
+    /// Add a line, e.g. a fold line to score or a decoration to engrave. The line is run
+    /// after earlier lines of the same operation and before lines of later operations.
+    pub fn add_cut(&mut self, operation: Operation, path: Polyline) {
+        let index = self
+            .cuts
+            .iter()
+            .position(|cut| cut.operation > operation)
+            .unwrap_or(self.cuts.len());
+        self.cuts.insert(index, Cut { operation, path });
+    }
+
+    /// Add text to engrave, centred on the position
+    pub fn add_text(&mut self, text: &str, position: Point, size: f32) {
+        self.texts.push(Text {
+            text: text.to_string(),
+            position,
+            size,
+        });
+    }
+
+    /// Identifier of the part, e.g. `side-panel-2`. Stays the same whatever order the parts
+    /// are cut in.
+    pub fn id(&self) -> String {
+        format!("{}-{}", self.kind.id(), self.number)
+    }
+
+    /// Name of the part for people, e.g. `Side panel 2`
+    pub fn label(&self) -> String {
+        let kind = self.kind.to_string();
+        let mut characters = kind.chars();
+        let capitalised: String = characters
+            .next()
+            .map(|first| first.to_uppercase().chain(characters).collect())
+            .unwrap_or_default();
+        format!("{} {}", capitalised, self.number)
+    }
+
+    /// Whether the point is in the material of the part, inside the outline and outside holes
+    pub fn contains(&self, point: Point) -> bool {
+        self.outline.contains(point) && !self.holes.iter().any(|hole| hole.contains(point))
+    }
+
+    /// Area of the material left in the part
+    pub fn area(&self) -> f32 {
+        self.outline.area() - self.holes.iter().map(Polygon::area).sum::<f32>()
+    }
+}
+
+/// Bounding rectangle of a part, in mm
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PartSize {
+    pub kind: PartKind,
+    pub width: f32,
+    pub height: f32,
+    /// Number of parts inside the rectangle, side wings are nested in pairs
+    pub pieces: usize,
+}
+
+/// Bounding rectangles of every part of a rack, in the same orientation as in the SVG
+pub fn part_sizes(
+    rows: usize,
+    columns: usize,
+    material_thickness: f32,
+    dimensions: &ContainerDimensions,
+) -> Vec<PartSize> {
+    let column_width = dimensions.width + CLEARANCE_FOR_CONTAINER_WIDTH;
+    let wing_pair = PartSize {
+        kind: PartKind::SideWing,
+        width: dimensions.depth,
+        height: height_of_two_side_wings(
+            dimensions.side_wing_width,
+            material_thickness,
+            CLEARANCE_BETWEEN_PATHS,
+        ),
+        pieces: 2,
+    };
+    let cover = PartSize {
+        kind: PartKind::Cover,
+        width: top_width(column_width + material_thickness, columns, material_thickness),
+        height: dimensions.depth,
+        pieces: 1,
+    };
+    let side_panel = PartSize {
+        kind: PartKind::SidePanel,
+        width: dimensions.height * rows as f32 + 2.0 * material_thickness,
+        height: dimensions.depth,
+        pieces: 1,
+    };
+
+    let mut parts = vec![wing_pair; rows * columns];
+    parts.extend([cover; 2]);
+    parts.extend(vec![side_panel; columns + 1]);
+    parts
+}
+
+/// Outer dimensions of a rack once assembled
+pub fn assembled_dimensions(
+    rows: usize,
+    columns: usize,
+    material_thickness: f32,
+    dimensions: &ContainerDimensions,
+) -> AssembledDimensions {
+    let column_width = dimensions.width + CLEARANCE_FOR_CONTAINER_WIDTH;
+
+    AssembledDimensions {
+        width: (column_width * columns as f32) + (columns + 1) as f32 * material_thickness,
+        height: (dimensions.height * rows as f32) + material_thickness * 2.0,
+        depth: dimensions.depth,
+    }
+}
+
+/// Options for generating the SVG
+#[derive(Debug, Clone, PartialEq)]
+pub struct GenerationOptions {
+    /// Color and layer of each operation
+    pub operations: OperationStyles,
+    /// Unit of the document size
+    pub unit: Unit,
+    /// Place similar parts edge to edge and cut their shared edges only once
+    pub common_line: bool,
+    /// Tabs holding parts in the sheet, none by default
+    pub micro_tabs: Option<MicroTabs>,
+    /// Laser software the SVG is written for
+    pub profile: LaserSoftware,
+    /// Engrave the label of every part on it, e.g. `Side panel 2`
+    pub engrave_labels: bool,
+}
+
+impl Default for GenerationOptions {
+    fn default() -> Self {
+        GenerationOptions {
+            operations: OperationStyles::default(),
+            unit: Unit::Millimeter,
+            common_line: false,
+            micro_tabs: None,
+            profile: LaserSoftware::default(),
+            engrave_labels: false,
+        }
+    }
+}
+
 pub fn generate_svg(
     rows: usize,
     columns: usize,
@@ -61,254 +457,424 @@ pub fn generate_svg(
     container: &Container,
     primary_color: &str,
     secondary_color: &str,
+    unit: Unit,
 ) -> GeneratedSvg {
+    generate_svg_with_options(
+        rows,
+        columns,
+        material_thickness,
+        container,
+        &GenerationOptions {
+            operations: OperationStyles::with_cut_colors(primary_color, secondary_color),
+            unit,
+            ..GenerationOptions::default()
+        },
+    )
+}
+
+/// Generate the rack with the given options. The SVG embeds the project it was generated
+/// from, unless an operation has a color [`ProjectColors`] can't hold.
+pub fn generate_svg_with_options(
+    rows: usize,
+    columns: usize,
+    material_thickness: f32,
+    container: &Container,
+    options: &GenerationOptions,
+) -> GeneratedSvg {
+    let styles = &options.operations;
+    let unit = options.unit;
+    let profile = options.profile.profile();
+    // Common line cutting needs parts of same kind edge to edge
+    let part_spacing = if options.common_line {
+        0.0
+    } else {
+        CLEARANCE_BETWEEN_PATHS
+    };
     let starting_point_x = 0.0;
     let starting_point_y = 0.0;
     let column_width = container.dimensions.width + CLEARANCE_FOR_CONTAINER_WIDTH;
-    let amount_of_boxes = (rows * columns) as usize;
-    let height_of_two_side_wings =
-        height_of_two_side_wings(container.dimensions.side_wing_width, material_thickness);
-    let height_of_two_side_wings_with_clearance =
-        height_of_two_side_wings + CLEARANCE_BETWEEN_PATHS as f32;
-
-    let total_width = (container.dimensions.depth + (CLEARANCE_BETWEEN_PATHS * 3)) as f32
-        + top_width(column_width as f32, columns, material_thickness)
-        + (container.dimensions.height * rows) as f32
+    // Columns of covers include the slot of the side panel between them
+    let cover_column_width = column_width + material_thickness;
+    let cover_width = top_width(cover_column_width, columns, material_thickness);
+    let amount_of_boxes = rows * columns;
+    let height_of_two_side_wings = height_of_two_side_wings(
+        container.dimensions.side_wing_width,
+        material_thickness,
+        part_spacing,
+    );
+    let height_of_two_side_wings_with_clearance = height_of_two_side_wings + part_spacing;
+
+    let total_width = (container.dimensions.depth + (CLEARANCE_BETWEEN_PATHS * 3.0))
+        + cover_width
+        + (container.dimensions.height * rows as f32)
         + (2.0 * material_thickness);
-    let total_height = vec![
+    let total_height = [
         amount_of_boxes as f32 * height_of_two_side_wings_with_clearance,
-        (2 * container.dimensions.depth + CLEARANCE_BETWEEN_PATHS) as f32,
-        ((columns + 1) * (container.dimensions.depth + CLEARANCE_BETWEEN_PATHS)) as f32,
+        2.0 * container.dimensions.depth + part_spacing,
+        (columns + 1) as f32 * (container.dimensions.depth + part_spacing),
     ]
     .iter()
     .cloned()
     .fold(f32::NEG_INFINITY, f32::max);
 
-    let mut document = Document::new()
+    // Drawing coordinates stay in mm, only the physical size of the document follows the unit
+    let document = Document::new()
+        .set("xmlns:inkscape", INKSCAPE_NAMESPACE)
         .set("viewBox", (0, 0, total_width, total_height))
-        .set("width", format!("{}mm", total_width))
-        .set("height", format!("{}mm", total_height));
+        .set("width", profile.document_size(total_width, unit))
+        .set("height", profile.document_size(total_height, unit));
+
+    let mut parts = vec![];
 
     // Generate side wings
     for i in 0..amount_of_boxes {
-        generate_side_wing_pair(
-            &mut document,
+        parts.extend(generate_side_wing_pair(
             &container.dimensions,
             starting_point_x,
             starting_point_y + height_of_two_side_wings_with_clearance * i as f32,
             material_thickness,
-            secondary_color,
-        );
+            part_spacing,
+        ));
     }
 
     // Generate top and bottom pieces
-    generate_top_and_bottom_pieces(
-        &mut document,
+    parts.extend(generate_top_and_bottom_pieces(
         &container.dimensions,
-        (container.dimensions.depth + CLEARANCE_BETWEEN_PATHS) as f32,
+        container.dimensions.depth + CLEARANCE_BETWEEN_PATHS,
         columns,
-        column_width as f32 + material_thickness,
+        cover_column_width,
         material_thickness,
-        primary_color,
-        secondary_color,
-    );
+        part_spacing,
+    ));
 
     // generate side panels
-    generate_side_panels(
-        &mut document,
-        (container.dimensions.depth + CLEARANCE_BETWEEN_PATHS) as f32 //side wings
-            + top_width(column_width as f32, columns, material_thickness) + CLEARANCE_BETWEEN_PATHS as f32,
+    parts.extend(generate_side_panels(
+        container.dimensions.depth + CLEARANCE_BETWEEN_PATHS //side wings
+            + cover_width + CLEARANCE_BETWEEN_PATHS,
         &container.dimensions, // top and bottom plates
         rows,
         columns,
         material_thickness,
-        primary_color,
-        secondary_color,
-    );
+        part_spacing,
+    ));
+
+    // Parts are numbered before ordering so their ids don't depend on the cutting order
+    let parts = number_parts(parts);
+    let mut parts = order_for_cutting(parts);
+    if options.common_line {
+        share_common_lines(&mut parts);
+    }
+    if let Some(micro_tabs) = &options.micro_tabs {
+        add_micro_tabs(&mut parts, micro_tabs);
+    }
+    if options.engrave_labels {
+        add_labels(&mut parts);
+    }
+    let mut document = draw_parts(document, &parts, options);
+
+    // Embed the parameters so the design can be inspected and regenerated later. Colors a
+    // project can't hold would be regenerated differently, so those SVGs get no metadata.
+    if let Ok(colors) = ProjectColors::from_styles(styles) {
+        let project = RackProject {
+            rows,
+            columns,
+            material_thickness: Length::millimeters(material_thickness),
+            container: ProjectContainer::Inline(container.clone()),
+            colors,
+            layers: ProjectLayers {
+                cut_inner: styles.cut_inner.layer.clone(),
+                cut_outer: styles.cut_outer.layer.clone(),
+                score: styles.score.layer.clone(),
+                engrave_fill: styles.engrave_fill.layer.clone(),
+                engrave_text: styles.engrave_text.layer.clone(),
+            },
+            output: OutputSettings {
+                unit: Some(unit),
+                filename: None,
+                profile: options.profile,
+                engrave_labels: options.engrave_labels,
+            },
+            cutting: CuttingSettings {
+                common_line: options.common_line,
+                micro_tabs: options.micro_tabs.clone(),
+            },
+        };
+        document.append(DesignMetadata::new(project).to_element());
+    }
 
-    // Calculate assembled dimensions
-    let assembled_width = (column_width * columns) as f32 
-        + (columns + 1) as f32 * material_thickness;
-    
-    let assembled_height = (container.dimensions.height * rows) as f32 
-        + material_thickness * 2.0;
-    
-    let assembled_depth = container.dimensions.depth as f32;
-    
     GeneratedSvg {
         document,
-        assembled_dimensions: AssembledDimensions {
-            width: assembled_width,
-            height: assembled_height,
-            depth: assembled_depth
+        assembled_dimensions: assembled_dimensions(
+            rows,
+            columns,
+            material_thickness,
+            &container.dimensions,
+        ),
+        sheet_dimensions: SheetDimensions {
+            width: total_width,
+            height: total_height,
+        },
+        stats: LayoutStats::new(&parts, styles),
+        parts,
+    }
+}
+
+/// Draw the parts on the document with the styles and laser software of the options.
+///
+/// Layers follow the order operations are run, and every part is a group in the order the
+/// operation visits the parts, see [`cutting_order`]. Without layers the part groups are
+/// appended in the same order.
+pub fn draw_parts(mut document: Document, parts: &[Part], options: &GenerationOptions) -> Document {
+    let profile = options.profile.profile();
+
+    for (operation, order) in cutting_order(parts) {
+        let style = options.operations.get(operation);
+        let groups: Vec<Group> = order
+            .into_iter()
+            .map(|index| &parts[index])
+            .map(|part| {
+                // Layers have a group of every part, so ids tell the operation too
+                let mut group = Group::new()
+                    .set("id", format!("{}-{}", part.id(), operation.id()))
+                    .set("inkscape:label", part.label());
+                for cut in part.cuts.iter().filter(|cut| cut.operation == operation) {
+                    group.append(cut_path(&cut.path, operation, &style.color, &profile));
+                }
+                if operation == Operation::EngraveText {
+                    for text in &part.texts {
+                        group.append(text_element(text, &style.color));
+                    }
+                }
+                group
+            })
+            .collect();
+        if groups.is_empty() {
+            continue;
+        }
+        if !profile.layers {
+            for group in groups {
+                document.append(group);
+            }
+            continue;
+        }
+
+        let mut layer = Group::new()
+            .set("id", operation.id())
+            .set("inkscape:groupmode", "layer")
+            .set("inkscape:label", style.layer.as_str());
+        for group in groups {
+            layer.append(group);
+        }
+        document.append(layer);
+    }
+
+    document
+}
+
+/// Engrave the label of every part in the middle of its outline
+fn add_labels(parts: &mut [Part]) {
+    for part in parts {
+        let bounds = part.outline.bounds();
+        let centre = Point::new(bounds.x + bounds.width / 2.0, bounds.y + bounds.height / 2.0);
+        let size = LABEL_SIZE.min(bounds.width.min(bounds.height) * 0.6);
+        let label = part.label();
+        part.add_text(&label, centre, size);
+    }
+}
+
+fn text_element(text: &Text, color: &str) -> svg::node::element::Text {
+    svg::node::element::Text::new(text.text.as_str())
+        .set("x", text.position.x)
+        .set("y", text.position.y)
+        .set("font-size", text.size)
+        .set("text-anchor", "middle")
+        .set("dominant-baseline", "central")
+        .set("fill", color)
+}
+
+/// Number parts of every kind from 1, in the order they are given
+fn number_parts(parts: Vec<Part>) -> Vec<Part> {
+    let mut counts: HashMap<PartKind, usize> = HashMap::new();
+
+    parts
+        .into_iter()
+        .map(|part| {
+            let count = counts.entry(part.kind).or_default();
+            *count += 1;
+            let number = *count;
+            part.numbered(number)
+        })
+        .collect()
+}
+
+fn cut_path(path: &Polyline, operation: Operation, color: &str, profile: &OutputProfile) -> Path {
+    let path_element = Path::new().set("d", path.to_data());
+
+    if operation.is_fill() {
+        path_element.set("fill", color).set("stroke", "none")
+    } else {
+        let path_element = path_element.set("fill", "none").set("stroke", color);
+        match profile.stroke_width {
+            Some(width) => path_element.set("stroke-width", width),
+            None => path_element,
         }
     }
 }
 
 fn generate_side_panels(
-    document: &mut Document,
     starting_point_x: f32,
     dimensions: &ContainerDimensions,
     rows: usize,
     columns: usize,
     material_thickness: f32,
-    primary_color: &str,
-    secondary_color: &str,
-) {
-    for i in 0..columns + 1 {
-        let y = (i * (dimensions.depth + CLEARANCE_BETWEEN_PATHS)) as f32;
+    spacing: f32,
+) -> Vec<Part> {
+    (0..columns + 1)
+        .map(|i| {
+            generate_side_panel(
+                starting_point_x,
+                i as f32 * (dimensions.depth + spacing),
+                dimensions,
+                rows,
+                material_thickness,
+            )
+        })
+        .collect()
+}
 
-        document.append(generate_side_panel_outline_path(
-            starting_point_x,
-            y,
-            dimensions,
-            rows,
-            material_thickness,
-            secondary_color,
-        ));
+fn generate_side_panel(
+    starting_point_x: f32,
+    starting_point_y: f32,
+    dimensions: &ContainerDimensions,
+    rows: usize,
+    material_thickness: f32,
+) -> Part {
+    let outline = generate_side_panel_outline(
+        starting_point_x,
+        starting_point_y,
+        dimensions,
+        rows,
+        material_thickness,
+    );
+    let mut holes = vec![];
 
-        for r in 0..rows {
-            let row_x = material_thickness
-                + (dimensions.side_wing_from_box_top + r * dimensions.height) as f32;
+    for r in 0..rows {
+        let row_x = material_thickness
+            + (dimensions.side_wing_from_box_top + r as f32 * dimensions.height);
 
-            document.append(generate_side_panel_wing_holes(
-                starting_point_x + row_x,
-                y + SIDE_WING_SLOT_FROM_FRONT as f32,
-                material_thickness,
-                primary_color,
-            ));
+        holes.push(generate_side_panel_wing_hole(
+            starting_point_x + row_x,
+            starting_point_y + SIDE_WING_SLOT_FROM_FRONT,
+            material_thickness,
+        ));
 
-            document.append(generate_side_panel_wing_holes(
-                starting_point_x + row_x,
-                y + (SIDE_WING_SLOT_FROM_FRONT + SIDE_WING_SLOT_WIDTH + SIDE_WING_SLOT_SPACING)
-                    as f32,
-                material_thickness,
-                primary_color,
-            ));
+        holes.push(generate_side_panel_wing_hole(
+            starting_point_x + row_x,
+            starting_point_y
+                + (SIDE_WING_SLOT_FROM_FRONT + SIDE_WING_SLOT_WIDTH + SIDE_WING_SLOT_SPACING),
+            material_thickness,
+        ));
 
-            document.append(generate_side_panel_wing_holes(
-                starting_point_x + row_x,
-                y + (dimensions.depth
+        holes.push(generate_side_panel_wing_hole(
+            starting_point_x + row_x,
+            starting_point_y
+                + (dimensions.depth
                     - SIDE_WING_SLOT_FROM_FRONT
-                    - (2 * SIDE_WING_SLOT_WIDTH)
-                    - SIDE_WING_SLOT_SPACING) as f32,
-                material_thickness,
-                primary_color,
-            ));
-            document.append(generate_side_panel_wing_holes(
-                starting_point_x + row_x,
-                y + (dimensions.depth - SIDE_WING_SLOT_FROM_FRONT - SIDE_WING_SLOT_WIDTH) as f32,
-                material_thickness,
-                primary_color,
-            ));
-        }
+                    - (2.0 * SIDE_WING_SLOT_WIDTH)
+                    - SIDE_WING_SLOT_SPACING),
+            material_thickness,
+        ));
+        holes.push(generate_side_panel_wing_hole(
+            starting_point_x + row_x,
+            starting_point_y
+                + (dimensions.depth - SIDE_WING_SLOT_FROM_FRONT - SIDE_WING_SLOT_WIDTH),
+            material_thickness,
+        ));
     }
+
+    Part::new(PartKind::SidePanel, outline, holes)
 }
 
-fn generate_side_panel_wing_holes(x: f32, y: f32, material_thickness: f32, color: &str) -> Path {
-    let path_data = Data::new()
-        .move_to((x, y))
-        .vertical_line_to(y + SIDE_WING_SLOT_WIDTH as f32)
+fn generate_side_panel_wing_hole(x: f32, y: f32, material_thickness: f32) -> Polygon {
+    Polygon::starting_at(x, y)
+        .vertical_line_to(y + SIDE_WING_SLOT_WIDTH)
         .horizontal_line_to(x + material_thickness)
         .vertical_line_to(y)
-        .close();
-
-    Path::new()
-        .set("fill", "none")
-        .set("stroke", color)
-        .set("d", path_data)
 }
 
-fn generate_side_panel_outline_path(
+fn generate_side_panel_outline(
     starting_point_x: f32,
     starting_point_y: f32,
     dimensions: &ContainerDimensions,
     rows: usize,
     material_thickness: f32,
-    color: &str,
-) -> Path {
-    let panel_inner_height = (dimensions.height * rows) as f32;
-    let side_panel_path_data = Data::new()
-        .move_to((starting_point_x + material_thickness, starting_point_y))
-        .vertical_line_to(starting_point_y + SIDE_TAP_FROM_FRONT as f32)
+) -> Polygon {
+    let panel_inner_height = dimensions.height * rows as f32;
+
+    Polygon::starting_at(starting_point_x + material_thickness, starting_point_y)
+        .vertical_line_to(starting_point_y + SIDE_TAP_FROM_FRONT)
         .horizontal_line_to(starting_point_x)
-        .vertical_line_to(starting_point_y + (SIDE_TAP_FROM_FRONT + SIDE_TAP_WIDTH) as f32)
+        .vertical_line_to(starting_point_y + (SIDE_TAP_FROM_FRONT + SIDE_TAP_WIDTH))
         .horizontal_line_to(starting_point_x + material_thickness)
         .vertical_line_to(
-            starting_point_y + (dimensions.depth - SIDE_TAP_FROM_FRONT - SIDE_TAP_WIDTH) as f32,
+            starting_point_y + (dimensions.depth - SIDE_TAP_FROM_FRONT - SIDE_TAP_WIDTH),
         )
         .horizontal_line_to(starting_point_x)
-        .vertical_line_to(starting_point_y + (dimensions.depth - SIDE_TAP_FROM_FRONT) as f32)
+        .vertical_line_to(starting_point_y + (dimensions.depth - SIDE_TAP_FROM_FRONT))
         .horizontal_line_to(starting_point_x + material_thickness)
-        .vertical_line_to(starting_point_y + dimensions.depth as f32)
+        .vertical_line_to(starting_point_y + dimensions.depth)
         .horizontal_line_to(starting_point_x + panel_inner_height + (1.0 * material_thickness))
-        .vertical_line_to(starting_point_y + (dimensions.depth - SIDE_TAP_FROM_FRONT) as f32)
+        .vertical_line_to(starting_point_y + (dimensions.depth - SIDE_TAP_FROM_FRONT))
         .horizontal_line_to(starting_point_x + panel_inner_height + (2.0 * material_thickness))
         .vertical_line_to(
-            starting_point_y + (dimensions.depth - SIDE_TAP_FROM_FRONT - SIDE_TAP_WIDTH) as f32,
+            starting_point_y + (dimensions.depth - SIDE_TAP_FROM_FRONT - SIDE_TAP_WIDTH),
         )
         .horizontal_line_to(starting_point_x + panel_inner_height + (1.0 * material_thickness))
-        .vertical_line_to(starting_point_y + (SIDE_TAP_FROM_FRONT + SIDE_TAP_WIDTH) as f32)
+        .vertical_line_to(starting_point_y + (SIDE_TAP_FROM_FRONT + SIDE_TAP_WIDTH))
         .horizontal_line_to(starting_point_x + panel_inner_height + (2.0 * material_thickness))
-        .vertical_line_to(starting_point_y + SIDE_TAP_FROM_FRONT as f32)
+        .vertical_line_to(starting_point_y + SIDE_TAP_FROM_FRONT)
         .horizontal_line_to(starting_point_x + panel_inner_height + (1.0 * material_thickness))
         .vertical_line_to(starting_point_y)
-        .close();
-
-    Path::new()
-        .set("fill", "none")
-        .set("stroke", color)
-        .set("d", side_panel_path_data)
 }
 
 fn generate_top_and_bottom_pieces(
-    document: &mut Document,
     dimensions: &ContainerDimensions,
     starting_point_x: f32,
     columns: usize,
     column_width: f32,
     material_thickness: f32,
-    primary_color: &str,
-    secondary_color: &str,
-) {
-    generate_cover_path(
-        document,
-        dimensions,
-        starting_point_x,
-        0.0,
-        columns,
-        column_width,
-        material_thickness,
-        primary_color,
-        secondary_color,
-    );
-
-    generate_cover_path(
-        document,
-        dimensions,
-        starting_point_x,
-        (dimensions.depth + CLEARANCE_BETWEEN_PATHS) as f32,
-        columns,
-        column_width,
-        material_thickness,
-        primary_color,
-        secondary_color,
-    );
+    spacing: f32,
+) -> [Part; 2] {
+    [
+        generate_cover(
+            dimensions,
+            starting_point_x,
+            0.0,
+            columns,
+            column_width,
+            material_thickness,
+        ),
+        generate_cover(
+            dimensions,
+            starting_point_x,
+            dimensions.depth + spacing,
+            columns,
+            column_width,
+            material_thickness,
+        ),
+    ]
 }
 
-fn generate_cover_path(
-    document: &mut Document,
+fn generate_cover(
     dimensions: &ContainerDimensions,
     starting_point_x: f32,
     starting_point_y: f32,
     columns: usize,
     column_width: f32,
     material_thickness: f32,
-    primary_color: &str,
-    secondary_color: &str,
-) {
+) -> Part {
     // Generate cover
-    let top_path_data = generate_top_path(
+    let outline = generate_top_outline(
         dimensions,
         starting_point_x,
         starting_point_y,
@@ -316,130 +882,113 @@ fn generate_cover_path(
         column_width,
         material_thickness,
     );
-    let path = Path::new()
-        .set("fill", "none")
-        .set("stroke", secondary_color)
-        .set("d", top_path_data);
-    document.append(path);
+    let mut holes = vec![];
 
     for i in 0..columns - 1 {
         let x = starting_point_x + column_width + (i as f32 * column_width);
-        let y = starting_point_y + SIDE_TAP_FROM_FRONT as f32;
-        let side_tap_hole_path = generate_side_tap_path(x, y, material_thickness, primary_color);
-        document.append(side_tap_hole_path);
+        let y = starting_point_y + SIDE_TAP_FROM_FRONT;
+        holes.push(generate_side_tap_hole(x, y, material_thickness));
 
-        let side_tap_hole_path = generate_side_tap_path(
+        holes.push(generate_side_tap_hole(
             x,
-            y + (dimensions.depth - SIDE_TAP_FROM_FRONT - (SIDE_TAP_WIDTH * 2)) as f32,
+            y + (dimensions.depth - SIDE_TAP_FROM_FRONT - (SIDE_TAP_WIDTH * 2.0)),
             material_thickness,
-            primary_color,
-        );
-        document.append(side_tap_hole_path);
+        ));
     }
 
     //Generate side panel taps to middle of cover
+    Part::new(PartKind::Cover, outline, holes)
 }
 
-fn generate_side_tap_path(x: f32, y: f32, material_thickness: f32, color: &str) -> Path {
-    let data = Data::new()
-        .move_to((x, y))
-        .vertical_line_to(y + SIDE_TAP_WIDTH as f32)
-        .horizontal_line_to(x + material_thickness as f32)
+fn generate_side_tap_hole(x: f32, y: f32, material_thickness: f32) -> Polygon {
+    Polygon::starting_at(x, y)
+        .vertical_line_to(y + SIDE_TAP_WIDTH)
+        .horizontal_line_to(x + material_thickness)
         .vertical_line_to(y)
-        .close();
-
-    Path::new()
-        .set("fill", "none")
-        .set("stroke", color)
-        .set("d", data)
 }
 
-fn generate_top_path(
+fn generate_top_outline(
     dimensions: &ContainerDimensions,
     starting_point_x: f32,
     starting_point_y: f32,
     columns: usize,
     column_width: f32,
     material_thickness: f32,
-) -> Data {
+) -> Polygon {
     let top_width = top_width(column_width, columns, material_thickness);
 
-    Data::new()
-        .move_to((starting_point_x, starting_point_y))
-        .vertical_line_to(starting_point_y + SIDE_TAP_FROM_FRONT as f32)
+    Polygon::starting_at(starting_point_x, starting_point_y)
+        .vertical_line_to(starting_point_y + SIDE_TAP_FROM_FRONT)
         .horizontal_line_to(starting_point_x + material_thickness)
-        .vertical_line_to(starting_point_y + (SIDE_TAP_FROM_FRONT + SIDE_TAP_WIDTH) as f32)
+        .vertical_line_to(starting_point_y + (SIDE_TAP_FROM_FRONT + SIDE_TAP_WIDTH))
         .horizontal_line_to(starting_point_x)
         .vertical_line_to(
-            starting_point_y + (dimensions.depth - (SIDE_TAP_FROM_FRONT + SIDE_TAP_WIDTH)) as f32,
+            starting_point_y + (dimensions.depth - (SIDE_TAP_FROM_FRONT + SIDE_TAP_WIDTH)),
         )
         .horizontal_line_to(starting_point_x + material_thickness)
-        .vertical_line_to(starting_point_y + (dimensions.depth - SIDE_TAP_FROM_FRONT) as f32)
+        .vertical_line_to(starting_point_y + (dimensions.depth - SIDE_TAP_FROM_FRONT))
         .horizontal_line_to(starting_point_x)
-        .vertical_line_to(starting_point_y + dimensions.depth as f32)
+        .vertical_line_to(starting_point_y + dimensions.depth)
         .horizontal_line_to(starting_point_x + top_width)
-        .vertical_line_to(starting_point_y + (dimensions.depth - SIDE_TAP_FROM_FRONT) as f32)
+        .vertical_line_to(starting_point_y + (dimensions.depth - SIDE_TAP_FROM_FRONT))
         .horizontal_line_to(starting_point_x - material_thickness + top_width)
         .vertical_line_to(
-            starting_point_y + (dimensions.depth - (SIDE_TAP_FROM_FRONT + SIDE_TAP_WIDTH)) as f32,
+            starting_point_y + (dimensions.depth - (SIDE_TAP_FROM_FRONT + SIDE_TAP_WIDTH)),
         )
         .horizontal_line_to(starting_point_x + top_width)
-        .vertical_line_to(starting_point_y + (SIDE_TAP_FROM_FRONT + SIDE_TAP_WIDTH) as f32)
+        .vertical_line_to(starting_point_y + (SIDE_TAP_FROM_FRONT + SIDE_TAP_WIDTH))
         .horizontal_line_to(starting_point_x - material_thickness + top_width)
-        .vertical_line_to(starting_point_y + SIDE_TAP_FROM_FRONT as f32)
+        .vertical_line_to(starting_point_y + SIDE_TAP_FROM_FRONT)
         .horizontal_line_to(starting_point_x + top_width)
         .vertical_line_to(starting_point_y)
-        .close()
 }
 
+/// Width of a cover. Every column includes the side panel on its left, and the side panel on
+/// the right of the last column comes on top.
 fn top_width(column_width: f32, columns: usize, material_thickness: f32) -> f32 {
-    (material_thickness + column_width * columns as f32) + material_thickness
+    column_width * columns as f32 + material_thickness
 }
 fn generate_side_wing_pair(
-    document: &mut Document,
     dimensions: &ContainerDimensions,
     starting_point_x: f32,
     starting_point_y: f32,
     material_thickness: f32,
-    color: &str,
-) {
-    let path = generate_side_wing(
+    spacing: f32,
+) -> [Part; 2] {
+    let wing = generate_side_wing(
         starting_point_x,
         starting_point_y,
         material_thickness,
         dimensions.depth,
         dimensions.side_wing_width,
         false,
-        &color,
     );
-    document.append(path);
-    let path = generate_side_wing(
+    let inverted_wing = generate_side_wing(
         starting_point_x,
-        starting_point_y + (dimensions.side_wing_width + CLEARANCE_BETWEEN_PATHS) as f32,
+        starting_point_y + (dimensions.side_wing_width + spacing),
         material_thickness,
         dimensions.depth,
         dimensions.side_wing_width,
         true,
-        &color,
     );
-    document.append(path);
+
+    [wing, inverted_wing].map(|outline| Part::new(PartKind::SideWing, outline, vec![]))
 }
 
-fn height_of_two_side_wings(side_wing_width: usize, material_thickness: f32) -> f32 {
-    (side_wing_width * 2 + CLEARANCE_BETWEEN_PATHS) as f32 + material_thickness
+fn height_of_two_side_wings(side_wing_width: f32, material_thickness: f32, spacing: f32) -> f32 {
+    (side_wing_width * 2.0 + spacing) + material_thickness
 }
 
 fn generate_side_wing(
     starting_point_x: f32,
     starting_point_y: f32,
     material_thickness: f32,
-    box_depth: usize,
-    box_side_wing_width: usize,
+    box_depth: f32,
+    box_side_wing_width: f32,
     inverted: bool,
-    color: &str,
-) -> Path {
-    let wing_data = if inverted {
-        generate_side_wing_inverted_path(
+) -> Polygon {
+    if inverted {
+        generate_side_wing_inverted_outline(
             starting_point_x,
             starting_point_y,
             material_thickness,
@@ -447,87 +996,84 @@ fn generate_side_wing(
             box_side_wing_width,
         )
     } else {
-        generate_side_wing_path(
+        generate_side_wing_outline(
             starting_point_x,
             starting_point_y,
             material_thickness,
             box_depth,
             box_side_wing_width,
         )
-    };
-
-    svg::node::element::Path::new()
-        .set("fill", "none")
-        .set("stroke", color)
-        .set("d", wing_data)
+    }
 }
 
-fn generate_side_wing_path(
+fn generate_side_wing_outline(
     starting_point_x: f32,
     starting_point_y: f32,
     material_thickness: f32,
-    box_depth: usize,
-    box_side_wing_width: usize,
-) -> Data {
-    Data::new()
-        .move_to((starting_point_x, starting_point_y))
-        .vertical_line_to(starting_point_y + box_side_wing_width as f32)
-        .horizontal_line_to(SIDE_WING_SLOT_FROM_FRONT)
-        .vertical_line_to(starting_point_y + material_thickness + box_side_wing_width as f32)
-        .horizontal_line_to(SIDE_WING_SLOT_FROM_FRONT + SIDE_WING_SLOT_WIDTH)
-        .vertical_line_to(starting_point_y + box_side_wing_width as f32)
-        .horizontal_line_to(third_side_wing_tap_position_from_front(box_depth))
-        .vertical_line_to(starting_point_y + box_side_wing_width as f32 + material_thickness)
+    box_depth: f32,
+    box_side_wing_width: f32,
+) -> Polygon {
+    Polygon::starting_at(starting_point_x, starting_point_y)
+        .vertical_line_to(starting_point_y + box_side_wing_width)
+        .horizontal_line_to(starting_point_x + SIDE_WING_SLOT_FROM_FRONT)
+        .vertical_line_to(starting_point_y + material_thickness + box_side_wing_width)
+        .horizontal_line_to(starting_point_x + SIDE_WING_SLOT_FROM_FRONT + SIDE_WING_SLOT_WIDTH)
+        .vertical_line_to(starting_point_y + box_side_wing_width)
+        .horizontal_line_to(starting_point_x + third_side_wing_tap_position_from_front(box_depth))
+        .vertical_line_to(starting_point_y + box_side_wing_width + material_thickness)
         .horizontal_line_to(
-            third_side_wing_tap_position_from_front(box_depth) + SIDE_WING_SLOT_WIDTH,
+            starting_point_x
+                + third_side_wing_tap_position_from_front(box_depth)
+                + SIDE_WING_SLOT_WIDTH,
         )
-        .vertical_line_to(starting_point_y + box_side_wing_width as f32)
-        .horizontal_line_to(box_depth)
+        .vertical_line_to(starting_point_y + box_side_wing_width)
+        .horizontal_line_to(starting_point_x + box_depth)
         .vertical_line_to(starting_point_y)
-        .close()
 }
 
-fn generate_side_wing_inverted_path(
+fn generate_side_wing_inverted_outline(
     starting_point_x: f32,
     starting_point_y: f32,
     material_thickness: f32,
-    box_depth: usize,
-    box_side_wing_width: usize,
-) -> Data {
-    Data::new()
-        .move_to((starting_point_x, starting_point_y + material_thickness))
-        .horizontal_line_to(second_side_wing_tap_position_from_front())
+    box_depth: f32,
+    box_side_wing_width: f32,
+) -> Polygon {
+    Polygon::starting_at(starting_point_x, starting_point_y + material_thickness)
+        .horizontal_line_to(starting_point_x + second_side_wing_tap_position_from_front())
         .vertical_line_to(starting_point_y)
-        .horizontal_line_to(second_side_wing_tap_position_from_front() + SIDE_WING_SLOT_WIDTH)
+        .horizontal_line_to(
+            starting_point_x + second_side_wing_tap_position_from_front() + SIDE_WING_SLOT_WIDTH,
+        )
         .vertical_line_to(starting_point_y + material_thickness)
-        .horizontal_line_to(fourth_side_wing_tap_position_from_front(box_depth))
+        .horizontal_line_to(starting_point_x + fourth_side_wing_tap_position_from_front(box_depth))
         .vertical_line_to(starting_point_y)
-        .horizontal_line_to(box_depth - SIDE_WING_SLOT_FROM_FRONT)
+        .horizontal_line_to(starting_point_x + box_depth - SIDE_WING_SLOT_FROM_FRONT)
         .vertical_line_to(starting_point_y + material_thickness)
-        .horizontal_line_to(box_depth)
-        .vertical_line_to(starting_point_y + material_thickness + box_side_wing_width as f32)
+        .horizontal_line_to(starting_point_x + box_depth)
+        .vertical_line_to(starting_point_y + material_thickness + box_side_wing_width)
         .horizontal_line_to(starting_point_x)
-        .close()
 }
 
-fn third_side_wing_tap_position_from_front(box_depth: usize) -> usize {
+fn third_side_wing_tap_position_from_front(box_depth: f32) -> f32 {
     box_depth
         - (SIDE_WING_SLOT_FROM_FRONT
             + SIDE_WING_SLOT_WIDTH
             + SIDE_WING_SLOT_SPACING
             + SIDE_WING_SLOT_WIDTH)
 }
-fn second_side_wing_tap_position_from_front() -> usize {
+fn second_side_wing_tap_position_from_front() -> f32 {
     SIDE_WING_SLOT_FROM_FRONT + SIDE_WING_SLOT_WIDTH + SIDE_WING_SLOT_SPACING
 }
 
-fn fourth_side_wing_tap_position_from_front(box_depth: usize) -> usize {
+fn fourth_side_wing_tap_position_from_front(box_depth: f32) -> f32 {
     box_depth - (SIDE_WING_SLOT_FROM_FRONT + SIDE_WING_SLOT_WIDTH)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::catalogue::{builtin_containers, find_container_in};
+    use crate::color::Color;
     use proptest::prelude::*;
 
     // Feature: calculate-assembled-dimensions, Property 1: Assembled Width Formula
@@ -538,7 +1084,7 @@ mod tests {
         #[test]
         fn test_assembled_width_formula(
             columns in 1usize..=10,
-            container_width in 50usize..=500,
+            container_width in 50.0f32..=500.0,
             material_thickness in 1.0f32..=20.0,
         ) {
             // Create a minimal container with the generated dimensions
@@ -549,10 +1095,10 @@ mod tests {
                 links: vec![],
                 dimensions: ContainerDimensions {
                     width: container_width,
-                    depth: 100,
-                    height: 100,
-                    side_wing_from_box_top: 10,
-                    side_wing_width: 20,
+                    depth: 100.0,
+                    height: 100.0,
+                    side_wing_from_box_top: 10.0,
+                    side_wing_width: 20.0,
                 },
             };
 
@@ -564,11 +1110,12 @@ mod tests {
                 &container,
                 "#000000",
                 "#FF0000",
+                Unit::Millimeter,
             );
 
             // Calculate expected width using the formula
             let column_width = container_width + CLEARANCE_FOR_CONTAINER_WIDTH;
-            let expected_width = (column_width * columns) as f32 
+            let expected_width = (column_width * columns as f32)
                 + (columns + 1) as f32 * material_thickness;
 
             // Verify the assembled width matches the formula
@@ -584,7 +1131,7 @@ mod tests {
         #[test]
         fn test_assembled_height_formula(
             rows in 1usize..=10,
-            container_height in 50usize..=500,
+            container_height in 50.0f32..=500.0,
             material_thickness in 1.0f32..=20.0,
         ) {
             // Create a minimal container with the generated dimensions
@@ -594,11 +1141,11 @@ mod tests {
                 description: "Test".to_string(),
                 links: vec![],
                 dimensions: ContainerDimensions {
-                    width: 100,
-                    depth: 100,
+                    width: 100.0,
+                    depth: 100.0,
                     height: container_height,
-                    side_wing_from_box_top: 10,
-                    side_wing_width: 20,
+                    side_wing_from_box_top: 10.0,
+                    side_wing_width: 20.0,
                 },
             };
 
@@ -610,10 +1157,11 @@ mod tests {
                 &container,
                 "#000000",
                 "#FF0000",
+                Unit::Millimeter,
             );
 
             // Calculate expected height using the formula
-            let expected_height = (container_height * rows) as f32 
+            let expected_height = (container_height * rows as f32)
                 + material_thickness * 2.0;
 
             // Verify the assembled height matches the formula
@@ -630,7 +1178,7 @@ mod tests {
         fn test_assembled_depth_equals_container_depth(
             rows in 1usize..=10,
             columns in 1usize..=10,
-            container_depth in 100usize..=500,
+            container_depth in 100.0f32..=500.0,
             material_thickness in 1.0f32..=20.0,
         ) {
             // Create a minimal container with the generated dimensions
@@ -640,11 +1188,11 @@ mod tests {
                 description: "Test".to_string(),
                 links: vec![],
                 dimensions: ContainerDimensions {
-                    width: 100,
+                    width: 100.0,
                     depth: container_depth,
-                    height: 100,
-                    side_wing_from_box_top: 10,
-                    side_wing_width: 20,
+                    height: 100.0,
+                    side_wing_from_box_top: 10.0,
+                    side_wing_width: 20.0,
                 },
             };
 
@@ -656,11 +1204,12 @@ mod tests {
                 &container,
                 "#000000",
                 "#FF0000",
+                Unit::Millimeter,
             );
 
             // Verify the assembled depth equals container depth
             // regardless of rows, columns, or material thickness
-            let expected_depth = container_depth as f32;
+            let expected_depth = container_depth;
             prop_assert_eq!(result.assembled_dimensions.depth, expected_depth);
         }
     }
@@ -674,9 +1223,9 @@ mod tests {
         fn test_all_dimensions_positive(
             rows in 1usize..=10,
             columns in 1usize..=10,
-            container_width in 50usize..=500,
-            container_height in 50usize..=500,
-            container_depth in 100usize..=500,
+            container_width in 50.0f32..=500.0,
+            container_height in 50.0f32..=500.0,
+            container_depth in 100.0f32..=500.0,
             material_thickness in 1.0f32..=20.0,
         ) {
             // Create a container with all positive input dimensions
@@ -689,8 +1238,8 @@ mod tests {
                     width: container_width,
                     depth: container_depth,
                     height: container_height,
-                    side_wing_from_box_top: 10,
-                    side_wing_width: 20,
+                    side_wing_from_box_top: 10.0,
+                    side_wing_width: 20.0,
                 },
             };
 
@@ -702,6 +1251,7 @@ mod tests {
                 &container,
                 "#000000",
                 "#FF0000",
+                Unit::Millimeter,
             );
 
             // Verify all three dimensions are positive
@@ -723,9 +1273,9 @@ mod tests {
         fn test_calculation_idempotence(
             rows in 1usize..=10,
             columns in 1usize..=10,
-            container_width in 50usize..=500,
-            container_height in 50usize..=500,
-            container_depth in 100usize..=500,
+            container_width in 50.0f32..=500.0,
+            container_height in 50.0f32..=500.0,
+            container_depth in 100.0f32..=500.0,
             material_thickness in 1.0f32..=20.0,
         ) {
             // Create a container with random dimensions
@@ -738,8 +1288,8 @@ mod tests {
                     width: container_width,
                     depth: container_depth,
                     height: container_height,
-                    side_wing_from_box_top: 10,
-                    side_wing_width: 20,
+                    side_wing_from_box_top: 10.0,
+                    side_wing_width: 20.0,
                 },
             };
 
@@ -751,6 +1301,7 @@ mod tests {
                 &container,
                 "#000000",
                 "#FF0000",
+                Unit::Millimeter,
             );
 
             let result2 = generate_svg(
@@ -760,6 +1311,7 @@ mod tests {
                 &container,
                 "#000000",
                 "#FF0000",
+                Unit::Millimeter,
             );
 
             // Verify both GeneratedSvg structures contain identical dimension values
@@ -772,6 +1324,76 @@ mod tests {
         }
     }
 
+    /// Every vertex of the moved part is the vertex of the original part moved by the offset
+    fn is_translated(moved: &Part, original: &Part, dx: f32, dy: f32) -> bool {
+        let polygons = |part: &Part| {
+            std::iter::once(part.outline.clone())
+                .chain(part.holes.clone())
+                .collect::<Vec<Polygon>>()
+        };
+        let moved = polygons(moved);
+        let original = polygons(original);
+
+        moved.len() == original.len()
+            && moved.iter().zip(&original).all(|(moved, original)| {
+                moved.points.len() == original.points.len()
+                    && moved.points.iter().zip(&original.points).all(|(moved, original)| {
+                        (moved.x - (original.x + dx)).abs() < 1e-3
+                            && (moved.y - (original.y + dy)).abs() < 1e-3
+                    })
+            })
+    }
+
+    // Moving the starting point of a part moves every vertex by the same offset, so a layout
+    // engine can place parts anywhere
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(100))]
+
+        #[test]
+        fn test_parts_are_translation_invariant(
+            dx in -500.0f32..=500.0,
+            dy in -500.0f32..=500.0,
+            rows in 1usize..=5,
+            columns in 1usize..=5,
+            material_thickness in 1.0f32..=10.0,
+        ) {
+            let dimensions = ContainerDimensions {
+                width: 170.0,
+                depth: 210.0,
+                height: 56.0,
+                side_wing_from_box_top: 15.0,
+                side_wing_width: 8.0,
+            };
+            let column_width = dimensions.width + CLEARANCE_FOR_CONTAINER_WIDTH + material_thickness;
+
+            let wings = generate_side_wing_pair(&dimensions, 0.0, 0.0, material_thickness, 3.0);
+            let moved_wings = generate_side_wing_pair(&dimensions, dx, dy, material_thickness, 3.0);
+            for (moved, original) in moved_wings.iter().zip(&wings) {
+                prop_assert!(is_translated(moved, original, dx, dy));
+            }
+
+            let cover = generate_cover(&dimensions, 0.0, 0.0, columns, column_width, material_thickness);
+            let moved_cover = generate_cover(&dimensions, dx, dy, columns, column_width, material_thickness);
+            prop_assert!(is_translated(&moved_cover, &cover, dx, dy));
+
+            let panel = generate_side_panel(0.0, 0.0, &dimensions, rows, material_thickness);
+            let moved_panel = generate_side_panel(dx, dy, &dimensions, rows, material_thickness);
+            prop_assert!(is_translated(&moved_panel, &panel, dx, dy));
+        }
+    }
+
+    #[test]
+    fn test_side_wings_start_from_their_starting_point() {
+        let outline = generate_side_wing(100.0, 50.0, 3.0, 210.0, 8.0, false);
+        let inverted = generate_side_wing(100.0, 50.0, 3.0, 210.0, 8.0, true);
+
+        for polygon in [outline, inverted] {
+            let bounds = polygon.bounds();
+            assert_eq!((bounds.x, bounds.y), (100.0, 50.0));
+            assert_eq!((bounds.width, bounds.height), (210.0, 11.0));
+        }
+    }
+
     // Unit tests for specific dimension calculations
     // **Validates: Requirements 1.1, 1.2, 1.3, 2.1, 2.2, 2.3, 3.1, 3.2, 3.3**
 
@@ -784,15 +1406,15 @@ mod tests {
             description: "Test".to_string(),
             links: vec![],
             dimensions: ContainerDimensions {
-                width: 100,
-                depth: 100,
-                height: 100,
-                side_wing_from_box_top: 10,
-                side_wing_width: 20,
+                width: 100.0,
+                depth: 100.0,
+                height: 100.0,
+                side_wing_from_box_top: 10.0,
+                side_wing_width: 20.0,
             },
         };
 
-        let result = generate_svg(2, 3, 5.0, &container, "#000000", "#FF0000");
+        let result = generate_svg(2, 3, 5.0, &container, "#000000", "#FF0000", Unit::Millimeter);
 
         // Calculate expected values
         // Width: (100 + 4) * 3 + (3 + 1) * 5 = 312 + 20 = 332mm
@@ -816,15 +1438,15 @@ mod tests {
             description: "Test".to_string(),
             links: vec![],
             dimensions: ContainerDimensions {
-                width: 80,
-                depth: 120,
-                height: 60,
-                side_wing_from_box_top: 10,
-                side_wing_width: 20,
+                width: 80.0,
+                depth: 120.0,
+                height: 60.0,
+                side_wing_from_box_top: 10.0,
+                side_wing_width: 20.0,
             },
         };
 
-        let result = generate_svg(1, 1, 3.0, &container, "#000000", "#FF0000");
+        let result = generate_svg(1, 1, 3.0, &container, "#000000", "#FF0000", Unit::Millimeter);
 
         // Calculate expected values
         // Width: (80 + 4) * 1 + (1 + 1) * 3 = 84 + 6 = 90mm
@@ -848,15 +1470,15 @@ mod tests {
             description: "Test".to_string(),
             links: vec![],
             dimensions: ContainerDimensions {
-                width: 100,
-                depth: 100,
-                height: 100,
-                side_wing_from_box_top: 10,
-                side_wing_width: 20,
+                width: 100.0,
+                depth: 100.0,
+                height: 100.0,
+                side_wing_from_box_top: 10.0,
+                side_wing_width: 20.0,
             },
         };
 
-        let result = generate_svg(2, 2, 0.5, &container, "#000000", "#FF0000");
+        let result = generate_svg(2, 2, 0.5, &container, "#000000", "#FF0000", Unit::Millimeter);
 
         // Calculate expected values
         // Width: (100 + 4) * 2 + (2 + 1) * 0.5 = 208 + 1.5 = 209.5mm
@@ -880,15 +1502,15 @@ mod tests {
             description: "Test".to_string(),
             links: vec![],
             dimensions: ContainerDimensions {
-                width: 150,
-                depth: 200,
-                height: 120,
-                side_wing_from_box_top: 10,
-                side_wing_width: 20,
+                width: 150.0,
+                depth: 200.0,
+                height: 120.0,
+                side_wing_from_box_top: 10.0,
+                side_wing_width: 20.0,
             },
         };
 
-        let result = generate_svg(5, 5, 6.0, &container, "#000000", "#FF0000");
+        let result = generate_svg(5, 5, 6.0, &container, "#000000", "#FF0000", Unit::Millimeter);
 
         // Calculate expected values
         // Width: (150 + 4) * 5 + (5 + 1) * 6 = 770 + 36 = 806mm
@@ -912,15 +1534,15 @@ mod tests {
             description: "Test".to_string(),
             links: vec![],
             dimensions: ContainerDimensions {
-                width: 90,
-                depth: 110,
-                height: 70,
-                side_wing_from_box_top: 10,
-                side_wing_width: 20,
+                width: 90.0,
+                depth: 110.0,
+                height: 70.0,
+                side_wing_from_box_top: 10.0,
+                side_wing_width: 20.0,
             },
         };
 
-        let result = generate_svg(1, 4, 4.0, &container, "#000000", "#FF0000");
+        let result = generate_svg(1, 4, 4.0, &container, "#000000", "#FF0000", Unit::Millimeter);
 
         // Calculate expected values
         // Width: (90 + 4) * 4 + (4 + 1) * 4 = 376 + 20 = 396mm
@@ -944,15 +1566,15 @@ mod tests {
             description: "Test".to_string(),
             links: vec![],
             dimensions: ContainerDimensions {
-                width: 85,
-                depth: 95,
-                height: 65,
-                side_wing_from_box_top: 10,
-                side_wing_width: 20,
+                width: 85.0,
+                depth: 95.0,
+                height: 65.0,
+                side_wing_from_box_top: 10.0,
+                side_wing_width: 20.0,
             },
         };
 
-        let result = generate_svg(4, 1, 3.5, &container, "#000000", "#FF0000");
+        let result = generate_svg(4, 1, 3.5, &container, "#000000", "#FF0000", Unit::Millimeter);
 
         // Calculate expected values
         // Width: (85 + 4) * 1 + (1 + 1) * 3.5 = 89 + 7 = 96mm
@@ -966,4 +1588,399 @@ mod tests {
         assert_eq!(result.assembled_dimensions.height, expected_height);
         assert_eq!(result.assembled_dimensions.depth, expected_depth);
     }
+
+    #[test]
+    fn test_part_sizes() {
+        let dimensions = ContainerDimensions {
+            width: 170.0,
+            depth: 210.0,
+            height: 56.0,
+            side_wing_from_box_top: 15.0,
+            side_wing_width: 8.0,
+        };
+
+        let parts = part_sizes(8, 3, 4.0, &dimensions);
+        let count = |kind| parts.iter().filter(|part| part.kind == kind).count();
+
+        assert_eq!(count(PartKind::SideWing), 24);
+        assert_eq!(parts[0].pieces, 2);
+        assert_eq!(count(PartKind::Cover), 2);
+        assert_eq!(count(PartKind::SidePanel), 4);
+        assert_eq!(parts[0].height, 23.0);
+        // Covers are as wide as the assembled rack
+        assert_eq!(parts[24].width, 538.0);
+        assert_eq!(parts[24].width, assembled_dimensions(8, 3, 4.0, &dimensions).width);
+        assert_eq!(parts[26].width, 456.0);
+    }
+
+    #[test]
+    fn test_operations_are_layers_with_holes_before_outlines() {
+        let container = find_container_in(builtin_containers(), "smartstore-classic_1").unwrap();
+
+        let generated = generate_svg(2, 3, 4.0, &container, "black", "blue", Unit::Millimeter);
+        let svg = generated.document.to_string();
+        let inner = svg.find(r#"id="cut-inner""#).unwrap();
+        let outer = svg.find(r#"id="cut-outer""#).unwrap();
+        let (holes, outlines) = svg.split_at(outer);
+
+        assert!(inner < outer);
+        assert!(holes.contains(r#"inkscape:label="Cut inner""#));
+        assert!(outlines.contains(r#"inkscape:label="Cut outer""#));
+        assert!(!svg.contains(r#"id="score""#));
+        // Every part has an outline, only covers between columns and side panels have holes.
+        // The layer of holes starts with its own group.
+        assert_eq!(outlines.matches(r#"<g id=""#).count(), generated.parts.len());
+        assert_eq!(holes.matches(r#"<g id=""#).count(), 1 + 2 + 4);
+        assert!(holes[inner..]
+            .lines()
+            .filter(|line| line.starts_with("<path"))
+            .all(|path| path.contains(r#"stroke="black""#)));
+        assert!(outlines
+            .lines()
+            .filter(|line| line.starts_with("<path"))
+            .all(|path| path.contains(r#"stroke="blue""#)));
+    }
+
+    #[test]
+    fn test_parts_are_groups_with_stable_ids() {
+        let container = find_container_in(builtin_containers(), "smartstore-classic_1").unwrap();
+
+        let generated = generate_svg(2, 3, 4.0, &container, "black", "blue", Unit::Millimeter);
+        let svg = generated.document.to_string();
+        let group_ids: Vec<&str> = svg
+            .lines()
+            .filter_map(|line| line.strip_prefix(r#"<g id=""#))
+            .map(|line| &line[..line.find('"').unwrap()])
+            .collect();
+        let mut unique_ids = group_ids.clone();
+        unique_ids.sort();
+        unique_ids.dedup();
+
+        assert_eq!(unique_ids.len(), group_ids.len());
+        assert!(svg.contains(r#"<g id="side-panel-2-cut-inner" inkscape:label="Side panel 2">"#));
+        assert!(svg.contains(r#"<g id="side-wing-12-cut-outer" inkscape:label="Side wing 12">"#));
+        assert!(!svg.contains("side-wing-13"));
+
+        let cover = generated.parts.iter().find(|part| part.id() == "cover-1").unwrap();
+        assert_eq!(cover.label(), "Cover 1");
+    }
+
+    #[test]
+    fn test_scores_and_engravings_are_drawn_in_their_layers() {
+        let container = find_container_in(builtin_containers(), "smartstore-classic_1").unwrap();
+        let mut parts =
+            generate_svg_with_options(1, 1, 4.0, &container, &GenerationOptions::default()).parts;
+        let cover = parts.iter_mut().find(|part| part.id() == "cover-1").unwrap();
+        let bounds = cover.outline.bounds();
+        let fold = Polyline {
+            points: vec![
+                Point::new(bounds.x + 10.0, bounds.y + 80.0),
+                Point::new(bounds.x + 100.0, bounds.y + 80.0),
+            ],
+            closed: false,
+        };
+        let logo = Polygon::starting_at(bounds.x + 10.0, bounds.y + 90.0)
+            .vertical_line_to(bounds.y + 100.0)
+            .horizontal_line_to(bounds.x + 20.0)
+            .vertical_line_to(bounds.y + 90.0);
+        cover.add_cut(Operation::Score, fold);
+        cover.add_cut(Operation::EngraveFill, Polyline::from(&logo));
+        cover.add_text("Top", Point::new(bounds.x + 50.0, bounds.y + 120.0), 5.0);
+
+        let cuts: Vec<Operation> = cover.cuts.iter().map(|cut| cut.operation).collect();
+        assert_eq!(&cuts[..2], [Operation::EngraveFill, Operation::Score]);
+        assert_eq!(cuts.last(), Some(&Operation::CutOuter));
+
+        let svg = draw_parts(Document::new(), &parts, &GenerationOptions::default()).to_string();
+        let layer = |id: &str| svg.find(&format!(r#"<g id="{}" inkscape:groupmode="layer""#, id));
+
+        let layers = ["engrave-fill", "engrave-text", "score", "cut-inner", "cut-outer"].map(layer);
+        assert!(layers.iter().all(Option::is_some), "{:?}", layers);
+        assert!(layers.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(svg.contains(r#"<g id="cover-1-score" inkscape:label="Cover 1">"#));
+        assert!(svg.contains(r#"fill="orange" font-size="5" text-anchor="middle""#));
+        assert!(svg.contains(">\nTop\n</text>"));
+    }
+
+    #[test]
+    fn test_part_labels_are_engraved() {
+        let container = find_container_in(builtin_containers(), "smartstore-classic_1").unwrap();
+        let options = GenerationOptions {
+            engrave_labels: true,
+            ..GenerationOptions::default()
+        };
+
+        let generated = generate_svg_with_options(1, 2, 4.0, &container, &options);
+        let svg = generated.document.to_string();
+
+        assert!(svg.contains(r#"<g id="engrave-text" inkscape:groupmode="layer""#));
+        assert!(svg.contains(">\nSide panel 3\n</text>"));
+        assert!(svg.contains(">\nSide wing 4\n</text>"));
+        assert!(generated.parts.iter().all(|part| part.texts.len() == 1));
+        let metadata = crate::metadata::inspect(&svg).unwrap();
+        assert!(metadata.project.output.engrave_labels);
+
+        let svg = generate_svg_with_options(1, 2, 4.0, &container, &GenerationOptions::default())
+            .document
+            .to_string();
+        assert!(!svg.contains("engrave-text"));
+    }
+
+    #[test]
+    fn test_metadata_keeps_colors() {
+        let container = find_container_in(builtin_containers(), "smartstore-classic_1").unwrap();
+
+        let svg = generate_svg(1, 1, 4.0, &container, "#00f", "Red", Unit::Millimeter).document.to_string();
+        let colors = crate::metadata::inspect(&svg).unwrap().project.colors;
+        assert_eq!(colors.primary, Color::Rgb(0, 0, 255));
+        assert_eq!(colors.secondary, Color::Named("red"));
+
+        // A project couldn't draw these lines again
+        let svg = generate_svg(1, 1, 4.0, &container, "currentColor", "red", Unit::Millimeter)
+            .document
+            .to_string();
+        assert!(svg.contains(r#"stroke="currentColor""#));
+        assert!(matches!(crate::metadata::inspect(&svg), Err(crate::metadata::MetadataError::Missing)));
+    }
+
+    #[test]
+    fn test_engraving_is_filled() {
+        let path = cut_path(
+            &Polyline::from(&Polygon::starting_at(0.0, 0.0).vertical_line_to(1.0).horizontal_line_to(1.0)),
+            Operation::EngraveFill,
+            "green",
+            &LaserSoftware::Trotec.profile(),
+        );
+
+        assert_eq!(
+            path.to_string(),
+            r#"<path d="M0,0 V1 H1 z" fill="green" stroke="none"/>"#
+        );
+    }
+
+    #[test]
+    fn test_output_profiles() {
+        let container = find_container_in(builtin_containers(), "smartstore-classic_1").unwrap();
+        let generate = |profile| {
+            let options = GenerationOptions {
+                profile,
+                ..GenerationOptions::default()
+            };
+            generate_svg_with_options(1, 1, 4.0, &container, &options).document.to_string()
+        };
+
+        let generic = generate(LaserSoftware::Generic);
+        assert!(generic.contains(r#"inkscape:groupmode="layer""#));
+        assert!(!generic.contains("stroke-width"));
+
+        let epilog = generate(LaserSoftware::Epilog);
+        assert!(!epilog.contains(r#"inkscape:groupmode="layer""#));
+        assert!(epilog
+            .lines()
+            .filter(|line| line.starts_with("<path"))
+            .all(|path| path.contains(r#"stroke-width="0.0254""#)));
+
+        let glowforge = generate(LaserSoftware::Glowforge);
+        let svg_element = glowforge.lines().find(|line| line.starts_with("<svg")).unwrap();
+        assert!(!svg_element.contains(r#"mm""#));
+    }
+
+    #[test]
+    fn test_common_line_cuts_shared_edges_once() {
+        let container = find_container_in(builtin_containers(), "smartstore-classic_1").unwrap();
+        let options = GenerationOptions {
+            common_line: true,
+            ..GenerationOptions::default()
+        };
+
+        let separate = generate_svg(2, 3, 4.0, &container, "black", "blue", Unit::Millimeter);
+        let shared = generate_svg_with_options(2, 3, 4.0, &container, &options);
+
+        assert_eq!(
+            separate.document.to_string(),
+            generate_svg_with_options(2, 3, 4.0, &container, &GenerationOptions::default())
+                .document
+                .to_string()
+        );
+        assert_eq!(shared.parts.len(), separate.parts.len());
+        assert_eq!(shared.stats.part_area, separate.stats.part_area);
+        assert!(shared.stats.bounding_box.height < separate.stats.bounding_box.height);
+        // Covers and side panels share their long edges
+        let saved = separate.stats.total_cut_length() - shared.stats.total_cut_length();
+        let top_width = top_width(container.dimensions.width + 4.0 + 4.0, 3, 4.0);
+        assert!(saved > top_width);
+        assert!(shared
+            .parts
+            .iter()
+            .flat_map(|part| &part.cuts)
+            .any(|cut| !cut.path.closed));
+    }
+
+    #[test]
+    fn test_micro_tabs_in_side_wings() {
+        let container = find_container_in(builtin_containers(), "smartstore-classic_1").unwrap();
+        let options = GenerationOptions {
+            micro_tabs: Some(MicroTabs {
+                count: 2,
+                width: 0.5,
+                parts: vec![PartKind::SideWing],
+            }),
+            ..GenerationOptions::default()
+        };
+
+        let generated = generate_svg_with_options(2, 3, 4.0, &container, &options);
+
+        for part in &generated.parts {
+            let open_cuts = part.cuts.iter().filter(|cut| !cut.path.closed).count();
+            match part.kind {
+                PartKind::SideWing => assert_eq!(open_cuts, 2),
+                _ => assert_eq!(open_cuts, 0),
+            }
+        }
+        let metadata = crate::metadata::inspect(&generated.document.to_string()).unwrap();
+        assert_eq!(metadata.project.cutting.micro_tabs, options.micro_tabs);
+    }
+
+    #[test]
+    fn test_document_size_in_inches() {
+        let container = Container {
+            vendor: "Test".to_string(),
+            model: "Test".to_string(),
+            description: "Test".to_string(),
+            links: vec![],
+            dimensions: ContainerDimensions {
+                width: 100.0,
+                depth: 100.0,
+                height: 100.0,
+                side_wing_from_box_top: 10.0,
+                side_wing_width: 20.0,
+            },
+        };
+
+        let millimeters = generate_svg(1, 1, 5.0, &container, "#000000", "#FF0000", Unit::Millimeter);
+        let inches = generate_svg(1, 1, 5.0, &container, "#000000", "#FF0000", Unit::Inch);
+
+        let mm_attributes = millimeters.document.get_attributes().unwrap();
+        let in_attributes = inches.document.get_attributes().unwrap();
+        let mm_width = mm_attributes["width"].to_string();
+        let in_width = in_attributes["width"].to_string();
+
+        assert!(mm_width.ends_with("mm"));
+        assert!(in_width.ends_with("in"));
+        let mm_value: f32 = mm_width.trim_end_matches("mm").parse().unwrap();
+        let in_value: f32 = in_width.trim_end_matches("in").parse().unwrap();
+        assert!((in_value * 25.4 - mm_value).abs() < 0.001);
+
+        // Drawing itself stays in millimeters
+        assert_eq!(mm_attributes["viewBox"], in_attributes["viewBox"]);
+    }
+
+    #[test]
+    fn test_assembled_dimensions_to_inches() {
+        let dims = AssembledDimensions {
+            width: 254.0,
+            height: 127.0,
+            depth: 25.4,
+        };
+
+        let inches = dims.to_unit(Unit::Inch);
+
+        assert_eq!(inches.width, 10.0);
+        assert_eq!(inches.height, 5.0);
+        assert_eq!(inches.depth, 1.0);
+    }
+
+    #[test]
+    fn test_fractional_container_dimensions() {
+        let container = Container {
+            vendor: "Test".to_string(),
+            model: "Test".to_string(),
+            description: "Test".to_string(),
+            links: vec![],
+            dimensions: ContainerDimensions {
+                width: 170.5,
+                depth: 210.0,
+                height: 56.5,
+                side_wing_from_box_top: 15.0,
+                side_wing_width: 7.6,
+            },
+        };
+
+        let result = generate_svg(2, 2, 4.0, &container, "#000000", "#FF0000", Unit::Millimeter);
+
+        // Width: (170.5 + 4) * 2 + (2 + 1) * 4 = 349 + 12 = 361mm
+        assert_eq!(result.assembled_dimensions.width, 361.0);
+        // Height: 56.5 * 2 + 4 * 2 = 113 + 8 = 121mm
+        assert_eq!(result.assembled_dimensions.height, 121.0);
+        // Wing lip is not rounded
+        assert!(result.document.to_string().contains("V7.6 H20"));
+    }
+
+    #[test]
+    fn test_side_wing_path_for_integer_dimensions_is_unchanged() {
+        let dimensions = ContainerDimensions {
+            width: 170.0,
+            depth: 210.0,
+            height: 56.0,
+            side_wing_from_box_top: 15.0,
+            side_wing_width: 8.0,
+        };
+
+        let outline = generate_side_wing(0.0, 0.0, 3.0, dimensions.depth, dimensions.side_wing_width, false);
+        let path = cut_path(
+            &Polyline::from(&outline),
+            Operation::CutOuter,
+            "blue",
+            &OutputProfile::default(),
+        );
+
+        assert_eq!(
+            path.to_string(),
+            r#"<path d="M0,0 V8 H20 V11 H40 V8 H135 V11 H155 V8 H210 V0 z" fill="none" stroke="blue"/>"#
+        );
+    }
+
+    #[test]
+    fn test_containers_with_same_profile_fit_same_rack() {
+        let dimensions = |height: f32, width: f32| ContainerDimensions {
+            width,
+            depth: 210.0,
+            height,
+            side_wing_from_box_top: 15.0,
+            side_wing_width: 8.0,
+        };
+        let container = |height: f32, width: f32| Container {
+            vendor: "Test".to_string(),
+            model: "Test".to_string(),
+            description: "Test".to_string(),
+            links: vec![],
+            dimensions: dimensions(height, width),
+        };
+        let rack_container = container(56.0, 170.0);
+
+        assert!(container(126.0, 170.0).fits_rack_for(&rack_container));
+        assert!(container(56.0, 170.3).fits_rack_for(&rack_container));
+        assert!(!container(56.0, 180.0).fits_rack_for(&rack_container));
+
+        assert_eq!(container(56.0, 170.0).rows_needed_in_rack_for(&rack_container), 1);
+        assert_eq!(container(86.0, 170.0).rows_needed_in_rack_for(&rack_container), 2);
+        assert_eq!(container(126.0, 170.0).rows_needed_in_rack_for(&rack_container), 3);
+    }
+
+    #[test]
+    fn test_validate_dimensions() {
+        let mut dimensions = ContainerDimensions {
+            width: 170.0,
+            depth: 210.0,
+            height: 56.0,
+            side_wing_from_box_top: 15.0,
+            side_wing_width: 8.0,
+        };
+        assert!(dimensions.validate().is_ok());
+
+        dimensions.depth = MINIMUM_CONTAINER_DEPTH - 1.0;
+        dimensions.side_wing_from_box_top = 60.0;
+
+        assert_eq!(dimensions.validate().unwrap_err().len(), 2);
+    }
 }
@@ -0,0 +1,136 @@
+use serde::{Deserialize, Serialize};
+
+/// What the laser does along a line. Operations are listed in the order they are run: the
+/// sheet is engraved and scored while it is still in one piece, and outlines are cut last.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Operation {
+    /// Filled area engraved to the surface, e.g. decorations
+    EngraveFill,
+    /// Text engraved to the surface as filled glyphs, e.g. labels
+    EngraveText,
+    /// Shallow line which doesn't go through, e.g. fold lines
+    Score,
+    /// Holes and slots inside a part
+    CutInner,
+    /// Outline of a part
+    CutOuter,
+}
+
+impl Operation {
+    /// Every operation in the order they are run
+    pub const ALL: [Operation; 5] = [
+        Operation::EngraveFill,
+        Operation::EngraveText,
+        Operation::Score,
+        Operation::CutInner,
+        Operation::CutOuter,
+    ];
+
+    /// Identifier of the operation, e.g. `cut-inner`
+    pub fn id(&self) -> &'static str {
+        match self {
+            Operation::EngraveFill => "engrave-fill",
+            Operation::EngraveText => "engrave-text",
+            Operation::Score => "score",
+            Operation::CutInner => "cut-inner",
+            Operation::CutOuter => "cut-outer",
+        }
+    }
+
+    /// Engraved areas are filled, other operations follow the line
+    pub fn is_fill(&self) -> bool {
+        matches!(self, Operation::EngraveFill | Operation::EngraveText)
+    }
+
+    /// Cuts go through the material, other operations only mark its surface
+    pub fn is_cut(&self) -> bool {
+        matches!(self, Operation::CutInner | Operation::CutOuter)
+    }
+}
+
+/// Color of the lines of an operation and name of the SVG layer they are in
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OperationStyle {
+    pub color: String,
+    pub layer: String,
+}
+
+impl OperationStyle {
+    pub fn new(color: &str, layer: &str) -> Self {
+        OperationStyle {
+            color: color.to_string(),
+            layer: layer.to_string(),
+        }
+    }
+}
+
+/// Style of every operation. Laser software tells operations apart by color, so each
+/// operation should have its own.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OperationStyles {
+    pub engrave_fill: OperationStyle,
+    pub engrave_text: OperationStyle,
+    pub score: OperationStyle,
+    pub cut_inner: OperationStyle,
+    pub cut_outer: OperationStyle,
+}
+
+impl Default for OperationStyles {
+    fn default() -> Self {
+        OperationStyles {
+            engrave_fill: OperationStyle::new("green", "Engrave fill"),
+            engrave_text: OperationStyle::new("orange", "Engrave text"),
+            score: OperationStyle::new("red", "Score"),
+            cut_inner: OperationStyle::new("black", "Cut inner"),
+            cut_outer: OperationStyle::new("blue", "Cut outer"),
+        }
+    }
+}
+
+impl OperationStyles {
+    /// Default styles with given colors for holes and outlines
+    pub fn with_cut_colors(inner: &str, outer: &str) -> Self {
+        let mut styles = OperationStyles::default();
+        styles.cut_inner.color = inner.to_string();
+        styles.cut_outer.color = outer.to_string();
+        styles
+    }
+
+    pub fn get(&self, operation: Operation) -> &OperationStyle {
+        match operation {
+            Operation::EngraveFill => &self.engrave_fill,
+            Operation::EngraveText => &self.engrave_text,
+            Operation::Score => &self.score,
+            Operation::CutInner => &self.cut_inner,
+            Operation::CutOuter => &self.cut_outer,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_operations_run_in_order() {
+        let mut operations = Operation::ALL;
+        operations.sort();
+
+        assert_eq!(operations, Operation::ALL);
+        assert_eq!(operations.last(), Some(&Operation::CutOuter));
+    }
+
+    #[test]
+    fn test_default_colors_are_distinct() {
+        let styles = OperationStyles::default();
+        let mut colors: Vec<&str> = Operation::ALL
+            .iter()
+            .map(|operation| styles.get(*operation).color.as_str())
+            .collect();
+        colors.sort();
+        colors.dedup();
+
+        assert_eq!(colors.len(), Operation::ALL.len());
+    }
+}
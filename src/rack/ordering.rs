@@ -0,0 +1,226 @@
+use crate::rack::geometry::{Point, Polygon};
+use crate::rack::operations::Operation;
+use crate::rack::Part;
+
+fn distance(a: Point, b: Point) -> f32 {
+    (b.x - a.x).hypot(b.y - a.y)
+}
+
+/// Where cutting a polygon starts and ends
+fn start(polygon: &Polygon) -> Point {
+    polygon.points[0]
+}
+
+/// Order the cuts inside every part: holes are cut in a chain of closest holes, starting from
+/// the one closest to where the outline starts. Parts keep their order, [`cutting_order`]
+/// tells in which order parts are visited by every operation. Lines which don't cut through
+/// and texts are kept as they are.
+pub fn order_for_cutting(parts: Vec<Part>) -> Vec<Part> {
+    parts
+        .into_iter()
+        .map(|part| {
+            let holes = order_holes(part.holes, start(&part.outline));
+            let mut ordered = Part::new(part.kind, part.outline, holes).numbered(part.number);
+            for cut in part.cuts.into_iter().filter(|cut| !cut.operation.is_cut()) {
+                ordered.add_cut(cut.operation, cut.path);
+            }
+            ordered.texts = part.texts;
+            ordered
+        })
+        .collect()
+}
+
+/// Parts of every operation in the order they are cut, as indices to `parts`.
+///
+/// Operations are run one after another starting from the top left corner, like layers of
+/// the SVG, so all holes are cut before any outline and parts don't move before their holes
+/// are done. Inside an operation the next part is the one whose first cut starts closest to
+/// the laser head, and cuts of a part are run in their order. Texts of a part are engraved
+/// with [`Operation::EngraveText`] after its lines, starting from the first text.
+pub fn cutting_order(parts: &[Part]) -> Vec<(Operation, Vec<usize>)> {
+    let mut position = Point::new(0.0, 0.0);
+
+    Operation::ALL
+        .into_iter()
+        .map(|operation| {
+            let cuts = |index: usize| {
+                parts[index]
+                    .cuts
+                    .iter()
+                    .filter(move |cut| cut.operation == operation)
+            };
+            let texts = |index: usize| {
+                let texts: &[_] = if operation == Operation::EngraveText {
+                    &parts[index].texts
+                } else {
+                    &[]
+                };
+                texts.iter()
+            };
+            let mut remaining: Vec<usize> = (0..parts.len())
+                .filter(|index| cuts(*index).next().is_some() || texts(*index).next().is_some())
+                .collect();
+            let mut ordered = Vec::with_capacity(remaining.len());
+
+            while !remaining.is_empty() {
+                let entry_distance = |index: usize| {
+                    let entry = cuts(index)
+                        .next()
+                        .map(|cut| cut.path.start())
+                        .or_else(|| texts(index).next().map(|text| text.position));
+                    entry.map_or(f32::INFINITY, |entry| distance(position, entry))
+                };
+                let next = (0..remaining.len())
+                    .min_by(|&a, &b| {
+                        entry_distance(remaining[a]).total_cmp(&entry_distance(remaining[b]))
+                    })
+                    .expect("remaining parts is not empty");
+                let index = remaining.remove(next);
+
+                if let Some(last) = texts(index).next_back() {
+                    position = last.position;
+                } else if let Some(last) = cuts(index).next_back() {
+                    position = last.path.end();
+                }
+                ordered.push(index);
+            }
+
+            (operation, ordered)
+        })
+        .collect()
+}
+
+fn order_holes(holes: Vec<Polygon>, mut position: Point) -> Vec<Polygon> {
+    let mut remaining = holes;
+    let mut ordered = Vec::with_capacity(remaining.len());
+
+    while !remaining.is_empty() {
+        let next = (0..remaining.len())
+            .min_by(|&a, &b| {
+                distance(position, start(&remaining[a]))
+                    .total_cmp(&distance(position, start(&remaining[b])))
+            })
+            .expect("remaining holes is not empty");
+        let hole = remaining.remove(next);
+        position = start(&hole);
+        ordered.push(hole);
+    }
+
+    ordered
+}
+
+/// Distance the laser head moves between cuts when the parts are cut in [`cutting_order`],
+/// starting from the top left corner
+pub fn travel_length(parts: &[Part]) -> f32 {
+    let mut position = Point::new(0.0, 0.0);
+    let mut length = 0.0;
+
+    for (operation, order) in cutting_order(parts) {
+        let cuts = order
+            .into_iter()
+            .flat_map(|index| &parts[index].cuts)
+            .filter(|cut| cut.operation == operation);
+        for cut in cuts {
+            length += distance(position, cut.path.start());
+            position = cut.path.end();
+        }
+    }
+
+    length
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rack::geometry::Polyline;
+    use crate::rack::PartKind;
+
+    fn square(x: f32, y: f32, size: f32) -> Polygon {
+        Polygon::starting_at(x, y)
+            .vertical_line_to(y + size)
+            .horizontal_line_to(x + size)
+            .vertical_line_to(y)
+    }
+
+    fn part(x: f32, holes: Vec<Polygon>) -> Part {
+        Part::new(PartKind::Cover, square(x, 0.0, 50.0), holes)
+    }
+
+    #[test]
+    fn test_closest_part_is_cut_next() {
+        let parts = vec![part(200.0, vec![]), part(0.0, vec![]), part(100.0, vec![])];
+
+        let order = cutting_order(&parts);
+
+        assert_eq!(order.len(), Operation::ALL.len());
+        assert_eq!(order[3], (Operation::CutInner, vec![]));
+        assert_eq!(order[4], (Operation::CutOuter, vec![1, 2, 0]));
+    }
+
+    #[test]
+    fn test_holes_are_cut_before_outline() {
+        let parts = vec![part(
+            0.0,
+            vec![square(40.0, 10.0, 5.0), square(10.0, 10.0, 5.0)],
+        )
+        .numbered(3)];
+
+        let ordered = order_for_cutting(parts);
+        let cuts = &ordered[0].cuts;
+
+        assert_eq!(ordered[0].number, 3);
+        assert_eq!(cuts[0].path.start(), Point::new(10.0, 10.0));
+        assert_eq!(cuts[1].path.start(), Point::new(40.0, 10.0));
+        assert_eq!(cuts[2].path, Polyline::from(&ordered[0].outline));
+    }
+
+    #[test]
+    fn test_scores_and_texts_are_kept() {
+        let mut marked = part(0.0, vec![square(40.0, 10.0, 5.0), square(10.0, 10.0, 5.0)]);
+        let fold = Polyline {
+            points: vec![Point::new(0.0, 25.0), Point::new(50.0, 25.0)],
+            closed: false,
+        };
+        marked.add_cut(Operation::Score, fold.clone());
+        marked.add_text("Cover", Point::new(25.0, 40.0), 5.0);
+        let parts = vec![part(100.0, vec![]), marked];
+
+        let ordered = order_for_cutting(parts);
+        let order = cutting_order(&ordered);
+
+        assert_eq!(ordered[1].cuts[0].path, fold);
+        assert_eq!(ordered[1].cuts[1].path.start(), Point::new(10.0, 10.0));
+        assert_eq!(ordered[1].texts.len(), 1);
+        assert_eq!(order[1], (Operation::EngraveText, vec![1]));
+        assert_eq!(order[2], (Operation::Score, vec![1]));
+    }
+
+    #[test]
+    fn test_every_operation_is_ordered_separately() {
+        // Holes start from the small part in the corner, outlines from the big part next to
+        // its hole
+        let parts = vec![
+            Part::new(PartKind::Cover, square(30.0, 0.0, 100.0), vec![square(35.0, 90.0, 5.0)]),
+            Part::new(PartKind::Cover, square(0.0, 0.0, 20.0), vec![square(5.0, 5.0, 5.0)]),
+        ];
+
+        let order = cutting_order(&parts);
+
+        assert_eq!(order[3], (Operation::CutInner, vec![1, 0]));
+        assert_eq!(order[4], (Operation::CutOuter, vec![0, 1]));
+    }
+
+    #[test]
+    fn test_travel_follows_cutting_order() {
+        let parts = vec![
+            part(300.0, vec![]),
+            part(0.0, vec![square(20.0, 20.0, 5.0)]),
+            part(200.0, vec![]),
+            part(100.0, vec![]),
+        ];
+
+        // Hole of the part at 0, then outlines from left to right
+        let expected = 20.0 * 2f32.sqrt() + 20.0 * 2f32.sqrt() + 300.0;
+        assert!((travel_length(&parts) - expected).abs() < 1e-3);
+    }
+}
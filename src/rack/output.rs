@@ -0,0 +1,173 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::units::Unit;
+
+/// Width of a hairline for drivers which need lines of at most 0.001in to cut them, in mm
+const EPILOG_HAIRLINE: f32 = 0.0254;
+/// Width of a hairline in Trotec JobControl, in mm
+const TROTEC_HAIRLINE: f32 = 0.01;
+/// Visible line width for software which doesn't care about it, in mm
+const THIN_LINE: f32 = 0.1;
+/// Pixels per inch of SVGs sized in pixels
+const CSS_DPI: f32 = 96.0;
+
+/// How the SVG is written for laser software
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutputProfile {
+    /// Stroke width of lines in mm, left for the software to decide when not set
+    pub stroke_width: Option<f32>,
+    /// Unit the software expects the document size in
+    pub unit: Option<Unit>,
+    /// Document size is written in pixels at this many pixels per inch instead of a physical
+    /// unit, for software which assumes a fixed DPI
+    pub dpi: Option<f32>,
+    /// Parts are grouped to a layer per operation, otherwise part groups are at top level
+    pub layers: bool,
+}
+
+impl Default for OutputProfile {
+    fn default() -> Self {
+        LaserSoftware::Generic.profile()
+    }
+}
+
+impl OutputProfile {
+    /// Value of the `width` and `height` attributes for a size in mm
+    pub fn document_size(&self, millimeters: f32, unit: Unit) -> String {
+        match self.dpi {
+            Some(dpi) => format!("{}", Unit::Inch.from_mm(millimeters) * dpi),
+            None => format!("{}{}", unit.from_mm(millimeters), unit.suffix()),
+        }
+    }
+}
+
+/// Laser software with a known output profile
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LaserSoftware {
+    /// Plain SVG in the chosen unit with layers
+    #[default]
+    Generic,
+    LightBurn,
+    /// Epilog print driver, cuts only hairlines
+    Epilog,
+    /// Trotec JobControl, cuts only hairlines
+    Trotec,
+    /// xTool Creative Space
+    XTool,
+    Glowforge,
+}
+
+impl LaserSoftware {
+    pub const ALL: [LaserSoftware; 6] = [
+        LaserSoftware::Generic,
+        LaserSoftware::LightBurn,
+        LaserSoftware::Epilog,
+        LaserSoftware::Trotec,
+        LaserSoftware::XTool,
+        LaserSoftware::Glowforge,
+    ];
+
+    pub fn profile(&self) -> OutputProfile {
+        match self {
+            LaserSoftware::Generic => OutputProfile {
+                stroke_width: None,
+                unit: None,
+                dpi: None,
+                layers: true,
+            },
+            LaserSoftware::LightBurn => OutputProfile {
+                stroke_width: Some(THIN_LINE),
+                unit: Some(Unit::Millimeter),
+                dpi: None,
+                layers: true,
+            },
+            LaserSoftware::Epilog => OutputProfile {
+                stroke_width: Some(EPILOG_HAIRLINE),
+                unit: Some(Unit::Inch),
+                dpi: None,
+                layers: false,
+            },
+            LaserSoftware::Trotec => OutputProfile {
+                stroke_width: Some(TROTEC_HAIRLINE),
+                unit: Some(Unit::Millimeter),
+                dpi: None,
+                layers: false,
+            },
+            LaserSoftware::XTool => OutputProfile {
+                stroke_width: Some(THIN_LINE),
+                unit: Some(Unit::Millimeter),
+                dpi: None,
+                layers: true,
+            },
+            LaserSoftware::Glowforge => OutputProfile {
+                stroke_width: Some(THIN_LINE),
+                unit: None,
+                dpi: Some(CSS_DPI),
+                layers: false,
+            },
+        }
+    }
+}
+
+impl fmt::Display for LaserSoftware {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LaserSoftware::Generic => write!(f, "generic"),
+            LaserSoftware::LightBurn => write!(f, "lightburn"),
+            LaserSoftware::Epilog => write!(f, "epilog"),
+            LaserSoftware::Trotec => write!(f, "trotec"),
+            LaserSoftware::XTool => write!(f, "xtool"),
+            LaserSoftware::Glowforge => write!(f, "glowforge"),
+        }
+    }
+}
+
+impl FromStr for LaserSoftware {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalised = s.trim().to_lowercase().replace([' ', '-', '_'], "");
+
+        LaserSoftware::ALL
+            .into_iter()
+            .find(|software| software.to_string() == normalised)
+            .ok_or_else(|| {
+                format!(
+                    "Unknown output profile '{}', expected generic, lightburn, epilog, trotec, xtool or glowforge",
+                    s.trim()
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_software() {
+        for software in LaserSoftware::ALL {
+            assert_eq!(software.to_string().parse::<LaserSoftware>().unwrap(), software);
+        }
+        assert_eq!("Light Burn".parse::<LaserSoftware>().unwrap(), LaserSoftware::LightBurn);
+        assert!("corel".parse::<LaserSoftware>().is_err());
+    }
+
+    #[test]
+    fn test_document_size() {
+        assert_eq!(OutputProfile::default().document_size(25.4, Unit::Millimeter), "25.4mm");
+        assert_eq!(LaserSoftware::Epilog.profile().document_size(25.4, Unit::Inch), "1in");
+        assert_eq!(LaserSoftware::Glowforge.profile().document_size(25.4, Unit::Millimeter), "96");
+    }
+
+    #[test]
+    fn test_hairline_drivers_get_hairlines() {
+        for software in [LaserSoftware::Epilog, LaserSoftware::Trotec] {
+            assert!(software.profile().stroke_width.unwrap() <= EPILOG_HAIRLINE);
+        }
+    }
+}
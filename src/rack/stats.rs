@@ -0,0 +1,130 @@
+use std::collections::BTreeMap;
+
+use crate::color::{normalise_color, same_color};
+use crate::rack::geometry::Rect;
+use crate::rack::operations::OperationStyles;
+use crate::rack::{Part, PartKind};
+
+/// Material usage and cutting statistics of generated parts. Lengths are in mm.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayoutStats {
+    /// Smallest rectangle containing every part
+    pub bounding_box: Rect,
+    /// Area of the parts without their holes, in mm²
+    pub part_area: f32,
+    /// Share of the bounding box covered by parts, from 0 to 1
+    pub utilisation: f32,
+    pub part_counts: BTreeMap<PartKind, usize>,
+    /// Cut length per color. Colors are normalised, and the same color written differently
+    /// is counted once under the first way it is written.
+    pub cut_length_by_color: BTreeMap<String, f32>,
+}
+
+impl LayoutStats {
+    /// Statistics of parts styled with `styles`. Engraving is not counted in cut lengths.
+    pub fn new(parts: &[Part], styles: &OperationStyles) -> LayoutStats {
+        let bounding_box = parts
+            .iter()
+            .map(|part| part.outline.bounds())
+            .reduce(|a, b| a.union(&b))
+            .unwrap_or(Rect {
+                x: 0.0,
+                y: 0.0,
+                width: 0.0,
+                height: 0.0,
+            });
+        let part_area: f32 = parts.iter().map(Part::area).sum();
+
+        let mut part_counts = BTreeMap::new();
+        let mut cut_length_by_color = BTreeMap::new();
+        for part in parts {
+            *part_counts.entry(part.kind).or_default() += 1;
+            for cut in part.cuts.iter().filter(|cut| !cut.operation.is_fill()) {
+                let color = &styles.get(cut.operation).color;
+                let key = cut_length_by_color
+                    .keys()
+                    .find(|key: &&String| same_color(key, color))
+                    .cloned()
+                    .unwrap_or_else(|| normalise_color(color).unwrap_or_else(|_| color.clone()));
+                *cut_length_by_color.entry(key).or_default() += cut.path.length();
+            }
+        }
+
+        LayoutStats {
+            bounding_box,
+            part_area,
+            utilisation: if bounding_box.area() > 0.0 {
+                part_area / bounding_box.area()
+            } else {
+                0.0
+            },
+            part_counts,
+            cut_length_by_color,
+        }
+    }
+
+    /// Length of every cut together
+    pub fn total_cut_length(&self) -> f32 {
+        self.cut_length_by_color.values().sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rack::geometry::Polygon;
+
+    fn square(x: f32, size: f32) -> Polygon {
+        Polygon::starting_at(x, 0.0)
+            .vertical_line_to(size)
+            .horizontal_line_to(x + size)
+            .vertical_line_to(0.0)
+    }
+
+    #[test]
+    fn test_stats() {
+        let parts = [
+            Part::new(PartKind::Cover, square(0.0, 10.0), vec![square(2.0, 2.0)]),
+            Part::new(PartKind::SideWing, square(20.0, 10.0), vec![]),
+        ];
+
+        let stats = LayoutStats::new(&parts, &OperationStyles::default());
+
+        assert_eq!(stats.bounding_box.width, 30.0);
+        assert_eq!(stats.part_area, 196.0);
+        assert_eq!(stats.utilisation, 196.0 / 300.0);
+        assert_eq!(stats.part_counts[&PartKind::Cover], 1);
+        assert_eq!(stats.part_counts[&PartKind::SideWing], 1);
+        assert_eq!(stats.cut_length_by_color["blue"], 80.0);
+        assert_eq!(stats.cut_length_by_color["black"], 8.0);
+        assert_eq!(stats.total_cut_length(), 88.0);
+    }
+
+    #[test]
+    fn test_same_color_for_holes_and_outlines() {
+        let parts = [Part::new(
+            PartKind::Cover,
+            square(0.0, 10.0),
+            vec![square(2.0, 2.0)],
+        )];
+
+        let stats = LayoutStats::new(&parts, &OperationStyles::with_cut_colors("red", "red"));
+
+        assert_eq!(stats.cut_length_by_color.len(), 1);
+        assert_eq!(stats.cut_length_by_color["red"], 48.0);
+    }
+
+    #[test]
+    fn test_same_color_written_differently() {
+        let parts = [Part::new(
+            PartKind::Cover,
+            square(0.0, 10.0),
+            vec![square(2.0, 2.0)],
+        )];
+
+        let stats = LayoutStats::new(&parts, &OperationStyles::with_cut_colors("#00f", "Blue"));
+
+        assert_eq!(stats.cut_length_by_color.len(), 1);
+        assert_eq!(stats.cut_length_by_color["#0000FF"], 48.0);
+    }
+}
@@ -0,0 +1,338 @@
+use serde::{Deserialize, Serialize};
+
+use crate::rack::geometry::{join_segments, Point};
+use crate::rack::operations::Operation;
+use crate::rack::{Cut, Part, PartKind};
+use crate::validation::ValidationOptions;
+
+/// Tabs are kept this far from corners of the outline, i.e. from joint tabs and slots, in mm
+pub const MICRO_TAB_CLEARANCE: f32 = 5.0;
+/// Shortest cut left between two tabs on the same line, in mm
+pub const MICRO_TAB_MINIMUM_CUT: f32 = 2.0;
+
+/// Short gaps left in the outline cuts, so parts stay attached to the sheet instead of falling
+/// through or tilting on the bed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MicroTabs {
+    /// Tabs per part
+    #[serde(default = "default_count")]
+    pub count: usize,
+    /// Width of one tab in mm
+    #[serde(default = "default_width")]
+    pub width: f32,
+    /// Kinds of parts getting tabs
+    #[serde(default = "all_part_kinds")]
+    pub parts: Vec<PartKind>,
+}
+
+impl Default for MicroTabs {
+    fn default() -> Self {
+        MicroTabs {
+            count: default_count(),
+            width: default_width(),
+            parts: all_part_kinds(),
+        }
+    }
+}
+
+fn default_count() -> usize {
+    2
+}
+
+/// Narrowest tab which survives the default kerf, so default tabs pass validation
+fn default_width() -> f32 {
+    ValidationOptions::default().minimum_feature()
+}
+
+fn all_part_kinds() -> Vec<PartKind> {
+    vec![PartKind::SideWing, PartKind::Cover, PartKind::SidePanel]
+}
+
+impl MicroTabs {
+    /// Check the values which don't depend on the parts, see [`micro_tab_capacity`] for how
+    /// many tabs fit
+    pub fn validate(&self) -> Result<(), String> {
+        if self.width.is_finite() && self.width > 0.0 {
+            Ok(())
+        } else {
+            Err("Micro-tab width must be positive".to_string())
+        }
+    }
+}
+
+/// Straight stretch of an outline cut where centres of tabs can be
+struct Stretch {
+    cut: usize,
+    segment: usize,
+    /// Distance from the start of the segment where the stretch starts and ends
+    from: f32,
+    to: f32,
+}
+
+/// Stretches of the outline cuts of the part, at least `MICRO_TAB_CLEARANCE` and half a tab
+/// away from every corner
+fn stretches(part: &Part, width: f32) -> Vec<Stretch> {
+    let mut stretches = vec![];
+    for (cut_index, cut) in part.cuts.iter().enumerate() {
+        if cut.operation != Operation::CutOuter {
+            continue;
+        }
+        for (segment_index, (start, end)) in cut.path.segments().into_iter().enumerate() {
+            let length = (end.x - start.x).hypot(end.y - start.y);
+            let margin = MICRO_TAB_CLEARANCE + width / 2.0;
+            if length - 2.0 * margin >= 0.0 {
+                stretches.push(Stretch {
+                    cut: cut_index,
+                    segment: segment_index,
+                    from: margin,
+                    to: length - margin,
+                });
+            }
+        }
+    }
+    stretches
+}
+
+/// Most tabs of given width which fit the outline cuts of the part with at least
+/// `MICRO_TAB_MINIMUM_CUT` between them
+pub fn micro_tab_capacity(part: &Part, width: f32) -> usize {
+    let stretches = stretches(part, width);
+    let total: f32 = stretches.iter().map(|stretch| stretch.to - stretch.from).sum();
+
+    // Every stretch fits one tab, more tabs need room for a cut each
+    ((total / (width + MICRO_TAB_MINIMUM_CUT)) as usize).max(stretches.len().min(1))
+}
+
+/// Leave tabs in the outline cuts of selected parts.
+///
+/// Tabs are spread evenly along the straight lines of the outline, at least
+/// `MICRO_TAB_CLEARANCE` away from every corner. Parts get at most as many tabs as
+/// [`micro_tab_capacity`] allows, parts without room for a tab are left as they are.
+pub fn add_micro_tabs(parts: &mut [Part], tabs: &MicroTabs) {
+    if tabs.count == 0 || tabs.validate().is_err() {
+        return;
+    }
+
+    for part in parts.iter_mut().filter(|part| tabs.parts.contains(&part.kind)) {
+        let segments: Vec<Vec<(Point, Point)>> =
+            part.cuts.iter().map(|cut| cut.path.segments()).collect();
+        let stretches = stretches(part, tabs.width);
+        let count = tabs.count.min(micro_tab_capacity(part, tabs.width));
+        if count == 0 {
+            continue;
+        }
+
+        // Gaps as (cut, segment, from, to), centred evenly along the stretches
+        let total: f32 = stretches.iter().map(|stretch| stretch.to - stretch.from).sum();
+        let half = tabs.width / 2.0;
+        let mut gaps = vec![];
+        for i in 0..count {
+            let mut position = (i as f32 + 0.5) * total / count as f32;
+            for stretch in &stretches {
+                let length = stretch.to - stretch.from;
+                if position <= length {
+                    let centre = stretch.from + position;
+                    gaps.push((stretch.cut, stretch.segment, centre - half, centre + half));
+                    break;
+                }
+                position -= length;
+            }
+        }
+
+        let cuts = std::mem::take(&mut part.cuts);
+        for (cut_index, cut) in cuts.into_iter().enumerate() {
+            if !gaps.iter().any(|gap| gap.0 == cut_index) {
+                part.cuts.push(cut);
+                continue;
+            }
+
+            let mut pieces = vec![];
+            for (segment_index, segment) in segments[cut_index].iter().enumerate() {
+                let mut segment_gaps: Vec<(f32, f32)> = gaps
+                    .iter()
+                    .filter(|gap| gap.0 == cut_index && gap.1 == segment_index)
+                    .map(|gap| (gap.2, gap.3))
+                    .collect();
+                segment_gaps.sort_by(|a, b| a.0.total_cmp(&b.0));
+                pieces.extend(split_segment(*segment, &segment_gaps));
+            }
+
+            part.cuts.extend(
+                join_segments(pieces, cut.path.closed)
+                    .into_iter()
+                    .map(|path| Cut {
+                        operation: cut.operation,
+                        path,
+                    }),
+            );
+        }
+    }
+}
+
+/// Pieces of the segment between sorted gaps, gaps are distances from the start.
+/// Overlapping gaps are merged, so no piece runs backwards.
+fn split_segment((start, end): (Point, Point), gaps: &[(f32, f32)]) -> Vec<(Point, Point)> {
+    let length = (end.x - start.x).hypot(end.y - start.y);
+    let point_at = |distance: f32| {
+        let ratio = distance / length;
+        Point::new(
+            start.x + (end.x - start.x) * ratio,
+            start.y + (end.y - start.y) * ratio,
+        )
+    };
+
+    let mut pieces = vec![];
+    let mut piece_start = 0.0;
+    for &(from, to) in gaps {
+        if from > piece_start {
+            pieces.push((point_at(piece_start), point_at(from.min(length))));
+        }
+        piece_start = f32::max(piece_start, to);
+    }
+    if piece_start < length {
+        pieces.push((point_at(piece_start), end));
+    }
+
+    pieces
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rack::geometry::Polygon;
+
+    fn rectangle(width: f32, height: f32) -> Polygon {
+        Polygon::starting_at(0.0, 0.0)
+            .vertical_line_to(height)
+            .horizontal_line_to(width)
+            .vertical_line_to(0.0)
+    }
+
+    fn tabs(count: usize, parts: Vec<PartKind>) -> MicroTabs {
+        MicroTabs {
+            count,
+            width: 1.0,
+            parts,
+        }
+    }
+
+    #[test]
+    fn test_tabs_leave_gaps_in_outline() {
+        let mut parts = [Part::new(
+            PartKind::SideWing,
+            rectangle(100.0, 8.0),
+            vec![],
+        )];
+
+        add_micro_tabs(&mut parts, &tabs(2, vec![PartKind::SideWing]));
+
+        let cuts = &parts[0].cuts;
+        assert_eq!(cuts.len(), 2);
+        assert!(cuts.iter().all(|cut| !cut.path.closed));
+        let length: f32 = cuts.iter().map(|cut| cut.path.length()).sum();
+        assert!((length - 214.0).abs() < 0.001);
+        // Short ends of the wing have no room for tabs, so the tabs are in the middle of the
+        // long sides
+        for cut in cuts {
+            assert_eq!(cut.path.start().x, cut.path.end().x);
+            assert!(cut.path.start().x == 49.5 || cut.path.start().x == 50.5);
+        }
+    }
+
+    #[test]
+    fn test_tabs_stay_away_from_corners() {
+        let outline = Polygon::starting_at(0.0, 0.0)
+            .vertical_line_to(8.0)
+            .horizontal_line_to(20.0)
+            .vertical_line_to(12.0)
+            .horizontal_line_to(40.0)
+            .vertical_line_to(8.0)
+            .horizontal_line_to(100.0)
+            .vertical_line_to(0.0);
+        let mut parts = [Part::new(PartKind::SideWing, outline.clone(), vec![])];
+
+        add_micro_tabs(&mut parts, &tabs(5, vec![PartKind::SideWing]));
+
+        for cut in &parts[0].cuts {
+            for end in [cut.path.start(), cut.path.end()] {
+                let closest_corner = outline
+                    .points
+                    .iter()
+                    .map(|corner| (corner.x - end.x).hypot(corner.y - end.y))
+                    .fold(f32::INFINITY, f32::min);
+                assert!(closest_corner >= MICRO_TAB_CLEARANCE);
+            }
+        }
+        assert_eq!(parts[0].cuts.len(), 5);
+    }
+
+    #[test]
+    fn test_only_selected_parts_get_tabs() {
+        let mut parts = [
+            Part::new(PartKind::SideWing, rectangle(100.0, 8.0), vec![]),
+            Part::new(PartKind::Cover, rectangle(100.0, 100.0), vec![rectangle(20.0, 20.0)]),
+        ];
+        let cover = parts[1].clone();
+
+        add_micro_tabs(&mut parts, &tabs(2, vec![PartKind::SideWing]));
+
+        assert_eq!(parts[1], cover);
+    }
+
+    #[test]
+    fn test_holes_are_not_tabbed() {
+        let hole = rectangle(20.0, 20.0).translate(40.0, 40.0);
+        let mut parts = [Part::new(PartKind::Cover, rectangle(100.0, 100.0), vec![hole])];
+
+        add_micro_tabs(&mut parts, &MicroTabs::default());
+
+        assert_eq!(parts[0].cuts[0].operation, Operation::CutInner);
+        assert!(parts[0].cuts[0].path.closed);
+        assert_eq!(parts[0].cuts.len(), 3);
+    }
+
+    #[test]
+    fn test_too_many_tabs_are_capped() {
+        let mut parts = [Part::new(PartKind::SideWing, rectangle(100.0, 8.0), vec![])];
+        // Long sides have 89mm for centres of 1mm tabs, each tab takes 3mm with its cut
+        assert_eq!(micro_tab_capacity(&parts[0], 1.0), 59);
+
+        add_micro_tabs(&mut parts, &tabs(2000, vec![PartKind::SideWing]));
+
+        let cuts = &parts[0].cuts;
+        assert_eq!(cuts.len(), 59);
+        // No piece runs backwards over another one
+        let length: f32 = cuts.iter().map(|cut| cut.path.length()).sum();
+        assert!((length - (216.0 - 59.0)).abs() < 0.01);
+        for cut in cuts {
+            assert!(cut.path.length() >= MICRO_TAB_MINIMUM_CUT - 0.001);
+        }
+    }
+
+    #[test]
+    fn test_overlapping_gaps_are_merged() {
+        let segment = (Point::new(0.0, 0.0), Point::new(10.0, 0.0));
+
+        let pieces = split_segment(segment, &[(2.0, 5.0), (4.0, 6.0), (6.0, 7.0)]);
+
+        assert_eq!(
+            pieces,
+            [
+                (Point::new(0.0, 0.0), Point::new(2.0, 0.0)),
+                (Point::new(7.0, 0.0), Point::new(10.0, 0.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tab_width_must_be_positive() {
+        assert!(MicroTabs::default().validate().is_ok());
+        for width in [0.0, -1.0, f32::NAN] {
+            let tabs = MicroTabs {
+                width,
+                ..MicroTabs::default()
+            };
+            assert!(tabs.validate().is_err());
+        }
+    }
+}
@@ -0,0 +1,331 @@
+use std::fmt;
+
+use crate::rack::{part_sizes, ContainerDimensions, PartSize, SheetDimensions};
+
+/// Gap kept between parts and from the sheet edges, in mm
+const PART_SPACING: f32 = 3.0;
+
+/// Part placed on a sheet
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlacedPart {
+    pub part: PartSize,
+    /// Index of the sheet the part is on
+    pub sheet: usize,
+    pub x: f32,
+    pub y: f32,
+    /// Whether the part is turned a quarter around
+    pub rotated: bool,
+}
+
+/// Parts laid out on stock sheets
+#[derive(Debug, Clone)]
+pub struct SheetLayout {
+    pub sheet: SheetDimensions,
+    pub sheet_count: usize,
+    pub parts: Vec<PlacedPart>,
+}
+
+impl SheetLayout {
+    /// Total area of the bounding rectangles of the parts
+    pub fn part_area(&self) -> f32 {
+        self.parts
+            .iter()
+            .map(|placed| placed.part.width * placed.part.height)
+            .sum()
+    }
+
+    /// Share of the sheets not covered by parts, from 0 to 100. Nothing is wasted when no
+    /// sheet is used.
+    pub fn waste_percentage(&self) -> f32 {
+        if self.sheet_count == 0 {
+            return 0.0;
+        }
+        let sheet_area = self.sheet.area() * self.sheet_count as f32;
+        100.0 * (1.0 - self.part_area() / sheet_area)
+    }
+}
+
+/// Rack configuration evaluated against a stock sheet
+#[derive(Debug, Clone)]
+pub struct SheetPlan {
+    pub rows: usize,
+    pub columns: usize,
+    pub layout: SheetLayout,
+}
+
+impl SheetPlan {
+    pub fn boxes(&self) -> usize {
+        self.rows * self.columns
+    }
+
+    pub fn sheets_required(&self) -> usize {
+        self.layout.sheet_count
+    }
+
+    pub fn waste_percentage(&self) -> f32 {
+        self.layout.waste_percentage()
+    }
+
+    /// Boxes the rack holds per sheet used, 0 when no sheet is used
+    pub fn boxes_per_sheet(&self) -> f32 {
+        if self.sheets_required() == 0 {
+            return 0.0;
+        }
+        self.boxes() as f32 / self.sheets_required() as f32
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SheetError {
+    /// Part doesn't fit on an empty sheet in either orientation
+    PartTooLarge(PartSize),
+}
+
+impl fmt::Display for SheetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SheetError::PartTooLarge(part) => write!(
+                f,
+                "{} of {}mm x {}mm doesn't fit on the sheet",
+                part.kind, part.width, part.height
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SheetError {}
+
+/// Row of parts on a sheet, as tall as its first part
+struct Shelf {
+    sheet: usize,
+    y: f32,
+    height: f32,
+    used_width: f32,
+}
+
+/// Lay out parts on as few sheets as possible.
+///
+/// Parts are placed on shelves, tallest first. Long parts lie along the sheet width and are
+/// turned only when they don't fit otherwise.
+pub fn pack(parts: &[PartSize], sheet: SheetDimensions) -> Result<SheetLayout, SheetError> {
+    let usable_width = sheet.width - PART_SPACING;
+    let usable_height = sheet.height - PART_SPACING;
+
+    // Lay parts lengthwise along the sheet when they fit that way
+    let mut oriented: Vec<(PartSize, bool)> = parts
+        .iter()
+        .map(|part| {
+            let fits = |width: f32, height: f32| {
+                width + PART_SPACING <= usable_width && height + PART_SPACING <= usable_height
+            };
+            let lengthwise = part.width >= part.height;
+            if fits(part.width, part.height) && (lengthwise || !fits(part.height, part.width)) {
+                Ok((*part, false))
+            } else if fits(part.height, part.width) {
+                Ok((
+                    PartSize {
+                        width: part.height,
+                        height: part.width,
+                        ..*part
+                    },
+                    true,
+                ))
+            } else {
+                Err(SheetError::PartTooLarge(*part))
+            }
+        })
+        .collect::<Result<_, _>>()?;
+    oriented.sort_by(|(a, _), (b, _)| b.height.total_cmp(&a.height));
+
+    let mut shelves: Vec<Shelf> = vec![];
+    let mut used_heights: Vec<f32> = vec![];
+    let mut placed = vec![];
+
+    for (part, rotated) in oriented {
+        let width = part.width + PART_SPACING;
+        let height = part.height + PART_SPACING;
+
+        let shelf_index = match shelves
+            .iter()
+            .position(|shelf| height <= shelf.height && shelf.used_width + width <= usable_width)
+        {
+            Some(index) => index,
+            None => {
+                let sheet_index = match used_heights
+                    .iter()
+                    .position(|used| used + height <= usable_height)
+                {
+                    Some(index) => index,
+                    None => {
+                        used_heights.push(0.0);
+                        used_heights.len() - 1
+                    }
+                };
+                shelves.push(Shelf {
+                    sheet: sheet_index,
+                    y: used_heights[sheet_index],
+                    height,
+                    used_width: 0.0,
+                });
+                used_heights[sheet_index] += height;
+                shelves.len() - 1
+            }
+        };
+
+        let shelf = &mut shelves[shelf_index];
+        placed.push(PlacedPart {
+            part,
+            sheet: shelf.sheet,
+            x: PART_SPACING + shelf.used_width,
+            y: PART_SPACING + shelf.y,
+            rotated,
+        });
+        shelf.used_width += width;
+    }
+
+    Ok(SheetLayout {
+        sheet,
+        sheet_count: used_heights.len(),
+        parts: placed,
+    })
+}
+
+/// Lay out every rack from 1x1 up to `max_rows` x `max_columns` on the stock sheet.
+///
+/// Plans with least waste come first, then plans with most boxes per sheet. Configurations
+/// with parts larger than the sheet are left out.
+pub fn optimise(
+    dimensions: &ContainerDimensions,
+    material_thickness: f32,
+    sheet: SheetDimensions,
+    max_rows: usize,
+    max_columns: usize,
+) -> Vec<SheetPlan> {
+    let mut plans: Vec<SheetPlan> = (1..=max_rows)
+        .flat_map(|rows| (1..=max_columns).map(move |columns| (rows, columns)))
+        .filter_map(|(rows, columns)| {
+            let parts = part_sizes(rows, columns, material_thickness, dimensions);
+            let layout = pack(&parts, sheet).ok()?;
+            Some(SheetPlan {
+                rows,
+                columns,
+                layout,
+            })
+        })
+        .collect();
+
+    plans.sort_by(|a, b| {
+        a.waste_percentage()
+            .total_cmp(&b.waste_percentage())
+            .then(b.boxes_per_sheet().total_cmp(&a.boxes_per_sheet()))
+    });
+    plans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalogue::{builtin_containers, find_container_in};
+    use crate::rack::PartKind;
+
+    fn classic_1() -> ContainerDimensions {
+        find_container_in(builtin_containers(), "smartstore-classic_1").unwrap().dimensions
+    }
+
+    fn overlaps(a: &PlacedPart, b: &PlacedPart) -> bool {
+        a.sheet == b.sheet
+            && a.x < b.x + b.part.width
+            && b.x < a.x + a.part.width
+            && a.y < b.y + b.part.height
+            && b.y < a.y + a.part.height
+    }
+
+    #[test]
+    fn test_parts_stay_on_sheet_without_overlapping() {
+        let sheet = SheetDimensions {
+            width: 600.0,
+            height: 400.0,
+        };
+        let parts = part_sizes(8, 3, 4.0, &classic_1());
+
+        let layout = pack(&parts, sheet).unwrap();
+
+        assert_eq!(layout.parts.len(), parts.len());
+        assert!(layout.sheet_count > 1);
+        for (index, part) in layout.parts.iter().enumerate() {
+            assert!(part.x + part.part.width <= sheet.width);
+            assert!(part.y + part.part.height <= sheet.height);
+            assert!(part.sheet < layout.sheet_count);
+            assert!(layout.parts[index + 1..]
+                .iter()
+                .all(|other| !overlaps(part, other)));
+        }
+    }
+
+    #[test]
+    fn test_long_part_is_turned_to_fit() {
+        let sheet = SheetDimensions {
+            width: 300.0,
+            height: 600.0,
+        };
+        let part = PartSize {
+            kind: PartKind::Cover,
+            width: 500.0,
+            height: 210.0,
+            pieces: 1,
+        };
+
+        let layout = pack(&[part], sheet).unwrap();
+
+        assert!(layout.parts[0].rotated);
+        assert_eq!(layout.parts[0].part.width, 210.0);
+    }
+
+    #[test]
+    fn test_part_too_large() {
+        let sheet = SheetDimensions {
+            width: 100.0,
+            height: 100.0,
+        };
+
+        assert!(matches!(
+            pack(&part_sizes(1, 1, 4.0, &classic_1()), sheet),
+            Err(SheetError::PartTooLarge(_))
+        ));
+    }
+
+    #[test]
+    fn test_no_parts_use_no_sheets() {
+        let sheet = SheetDimensions {
+            width: 600.0,
+            height: 400.0,
+        };
+        let plan = SheetPlan {
+            rows: 1,
+            columns: 1,
+            layout: pack(&[], sheet).unwrap(),
+        };
+
+        assert_eq!(plan.sheets_required(), 0);
+        assert_eq!(plan.boxes_per_sheet(), 0.0);
+        assert_eq!(plan.waste_percentage(), 0.0);
+    }
+
+    #[test]
+    fn test_optimise_orders_by_waste() {
+        let sheet = SheetDimensions {
+            width: 600.0,
+            height: 450.0,
+        };
+
+        let plans = optimise(&classic_1(), 4.0, sheet, 8, 3);
+
+        assert_eq!(plans.len(), 24);
+        assert!(plans
+            .windows(2)
+            .all(|pair| pair[0].waste_percentage() <= pair[1].waste_percentage()));
+        assert!(plans
+            .iter()
+            .all(|plan| plan.waste_percentage() > 0.0 && plan.waste_percentage() < 100.0));
+    }
+}
@@ -0,0 +1,249 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+const MILLIMETERS_PER_INCH: f32 = 25.4;
+
+/// Unit of length used for inputs and outputs.
+///
+/// The library works in millimeters internally, units are converted at the edges.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Unit {
+    #[default]
+    Millimeter,
+    Inch,
+}
+
+impl Unit {
+    /// Suffix used when printing values, e.g. in SVG `width` and `height` attributes
+    pub fn suffix(&self) -> &'static str {
+        match self {
+            Unit::Millimeter => "mm",
+            Unit::Inch => "in",
+        }
+    }
+
+    /// Convert value given in this unit to millimeters
+    pub fn to_mm(&self, value: f32) -> f32 {
+        match self {
+            Unit::Millimeter => value,
+            Unit::Inch => value * MILLIMETERS_PER_INCH,
+        }
+    }
+
+    /// Convert value given in millimeters to this unit
+    pub fn from_mm(&self, millimeters: f32) -> f32 {
+        match self {
+            Unit::Millimeter => millimeters,
+            Unit::Inch => millimeters / MILLIMETERS_PER_INCH,
+        }
+    }
+}
+
+impl fmt::Display for Unit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.suffix())
+    }
+}
+
+impl FromStr for Unit {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "mm" | "millimeter" | "millimeters" | "millimetre" | "millimetres" => {
+                Ok(Unit::Millimeter)
+            }
+            "in" | "inch" | "inches" | "\"" => Ok(Unit::Inch),
+            other => Err(format!("Unknown unit '{}', expected 'mm' or 'in'", other)),
+        }
+    }
+}
+
+impl Serialize for Unit {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.suffix())
+    }
+}
+
+impl<'de> Deserialize<'de> for Unit {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// Length with its unit, e.g. `4mm` or `1/4in`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Length {
+    pub value: f32,
+    pub unit: Unit,
+}
+
+impl Length {
+    pub fn new(value: f32, unit: Unit) -> Self {
+        Length { value, unit }
+    }
+
+    pub fn millimeters(value: f32) -> Self {
+        Length::new(value, Unit::Millimeter)
+    }
+
+    pub fn inches(value: f32) -> Self {
+        Length::new(value, Unit::Inch)
+    }
+
+    /// Length in millimeters
+    pub fn to_mm(&self) -> f32 {
+        self.unit.to_mm(self.value)
+    }
+
+    /// Length converted to given unit
+    pub fn to_unit(&self, unit: Unit) -> Length {
+        Length::new(unit.from_mm(self.to_mm()), unit)
+    }
+}
+
+impl fmt::Display for Length {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.value, self.unit)
+    }
+}
+
+/// Parses lengths like `4`, `4mm`, `0.25in`, `1/4in`, `1 1/2"` or `1-1/2in`.
+///
+/// Values without unit are millimeters.
+impl FromStr for Length {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let trimmed = s.trim();
+        let number_end = trimmed
+            .find(|c: char| c.is_alphabetic() || c == '"')
+            .unwrap_or(trimmed.len());
+        let (number, unit) = trimmed.split_at(number_end);
+        let unit = if unit.is_empty() {
+            Unit::Millimeter
+        } else {
+            unit.parse()?
+        };
+        let value = parse_number(number.trim())
+            .ok_or_else(|| format!("Invalid length '{}', expected e.g. 4mm or 1/4in", s))?;
+
+        if value < 0.0 {
+            return Err(format!("Length '{}' cannot be negative", s));
+        }
+
+        Ok(Length::new(value, unit))
+    }
+}
+
+/// Serialized as text like `4mm` or `0.25in`
+impl Serialize for Length {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+/// Deserialized from text like `1/4in` or from a plain number of millimeters
+impl<'de> Deserialize<'de> for Length {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum RawLength {
+            Millimeters(f32),
+            Text(String),
+        }
+
+        match RawLength::deserialize(deserializer)? {
+            RawLength::Millimeters(value) => Ok(Length::millimeters(value)),
+            RawLength::Text(text) => text.parse().map_err(serde::de::Error::custom),
+        }
+    }
+}
+
+/// Parses decimal numbers, fractions and mixed numbers: `0.25`, `1/4`, `1 1/2`, `1-1/2`
+fn parse_number(number: &str) -> Option<f32> {
+    if number.is_empty() {
+        return None;
+    }
+
+    if let Some((whole, fraction)) = number.split_once([' ', '-']) {
+        if fraction.contains('/') && !whole.is_empty() {
+            return Some(whole.parse::<f32>().ok()? + parse_fraction(fraction.trim())?);
+        }
+    }
+
+    if number.contains('/') {
+        parse_fraction(number)
+    } else {
+        number.parse::<f32>().ok().filter(|value| value.is_finite())
+    }
+}
+
+fn parse_fraction(fraction: &str) -> Option<f32> {
+    let (numerator, denominator) = fraction.split_once('/')?;
+    let numerator = numerator.trim().parse::<f32>().ok()?;
+    let denominator = denominator.trim().parse::<f32>().ok()?;
+
+    if denominator == 0.0 {
+        None
+    } else {
+        Some(numerator / denominator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_plain_number_is_millimeters() {
+        assert_eq!("4".parse::<Length>().unwrap(), Length::millimeters(4.0));
+        assert_eq!("3.5".parse::<Length>().unwrap(), Length::millimeters(3.5));
+    }
+
+    #[test]
+    fn test_parse_units() {
+        assert_eq!("4mm".parse::<Length>().unwrap(), Length::millimeters(4.0));
+        assert_eq!("0.25in".parse::<Length>().unwrap(), Length::inches(0.25));
+        assert_eq!("0.25 in".parse::<Length>().unwrap(), Length::inches(0.25));
+        assert_eq!("0.25\"".parse::<Length>().unwrap(), Length::inches(0.25));
+    }
+
+    #[test]
+    fn test_parse_fractions() {
+        assert_eq!("1/4in".parse::<Length>().unwrap(), Length::inches(0.25));
+        assert_eq!("1/8\"".parse::<Length>().unwrap(), Length::inches(0.125));
+        assert_eq!("1 1/2in".parse::<Length>().unwrap(), Length::inches(1.5));
+        assert_eq!("1-1/2in".parse::<Length>().unwrap(), Length::inches(1.5));
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!("".parse::<Length>().is_err());
+        assert!("abc".parse::<Length>().is_err());
+        assert!("1/0in".parse::<Length>().is_err());
+        assert!("4ft".parse::<Length>().is_err());
+        assert!("-4mm".parse::<Length>().is_err());
+    }
+
+    #[test]
+    fn test_conversion() {
+        assert_eq!(Length::inches(0.25).to_mm(), 6.35);
+        assert_eq!(Length::millimeters(25.4).to_unit(Unit::Inch), Length::inches(1.0));
+        assert_eq!(Unit::Inch.from_mm(Unit::Inch.to_mm(3.0)), 3.0);
+    }
+
+    #[test]
+    fn test_serde() {
+        assert_eq!(serde_json::to_string(&Length::inches(0.25)).unwrap(), "\"0.25in\"");
+        assert_eq!(serde_json::to_string(&Unit::Inch).unwrap(), "\"in\"");
+        assert_eq!(serde_json::from_str::<Length>("\"1/4in\"").unwrap(), Length::inches(0.25));
+        assert_eq!(serde_json::from_str::<Length>("4.5").unwrap(), Length::millimeters(4.5));
+        assert_eq!(serde_json::from_str::<Unit>("\"mm\"").unwrap(), Unit::Millimeter);
+        assert!(serde_json::from_str::<Length>("\"4ft\"").is_err());
+    }
+}
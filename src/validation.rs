@@ -0,0 +1,726 @@
+use std::fmt;
+
+use crate::rack::common_line::uncut_pieces;
+use crate::rack::geometry::{Point, Polygon, Rect, EPSILON};
+use crate::rack::Part;
+
+/// Limits generated parts are checked against, in mm
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationOptions {
+    /// Width of the material the laser burns away
+    pub kerf: f32,
+    /// Features must be at least this many kerfs wide to survive cutting
+    pub kerf_multiple: f32,
+    /// Narrowest material allowed between a hole and the outline or another hole
+    pub minimum_web: f32,
+    /// Width of the micro-tabs left in outlines, other gaps in the cuts are missing cuts
+    pub micro_tab_width: Option<f32>,
+}
+
+impl Default for ValidationOptions {
+    fn default() -> Self {
+        ValidationOptions {
+            kerf: 0.2,
+            kerf_multiple: 3.0,
+            minimum_web: 2.0,
+            micro_tab_width: None,
+        }
+    }
+}
+
+impl ValidationOptions {
+    /// Narrowest feature allowed
+    pub fn minimum_feature(&self) -> f32 {
+        self.kerf * self.kerf_multiple
+    }
+}
+
+/// Outline or hole of a part. Holes are numbered from 0 in cutting order.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Shape {
+    Outline,
+    Hole(usize),
+}
+
+impl fmt::Display for Shape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Shape::Outline => write!(f, "outline"),
+            Shape::Hole(index) => write!(f, "hole {}", index + 1),
+        }
+    }
+}
+
+/// What is wrong with a part
+#[derive(Debug, Clone, PartialEq)]
+pub enum Problem {
+    /// Shape has no area or crosses itself, so it doesn't cut a piece out
+    OpenPath(Shape),
+    /// Part overlaps another part on the sheet
+    Overlap { other: String },
+    /// Hole reaches outside the outline of its part
+    HoleOutside(usize),
+    /// Too little material is left between a hole and the outline or another hole
+    ThinWeb {
+        hole: usize,
+        other: Shape,
+        width: f32,
+        minimum: f32,
+    },
+    /// Slot or piece of material too narrow to survive the kerf
+    NarrowFeature {
+        shape: Shape,
+        width: f32,
+        minimum: f32,
+    },
+    /// Stretch of the shape no cut runs along, which isn't a micro-tab
+    MissingCut { shape: Shape, length: f32 },
+    /// Micro-tab too narrow to survive the kerf
+    NarrowMicroTab { width: f32, minimum: f32 },
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Problem::OpenPath(shape) => write!(f, "{} is not a closed shape", shape),
+            Problem::Overlap { other } => write!(f, "overlaps {}", other),
+            Problem::HoleOutside(hole) => write!(f, "{} is outside the outline", Shape::Hole(*hole)),
+            Problem::ThinWeb {
+                hole,
+                other,
+                width,
+                minimum,
+            } => write!(
+                f,
+                "{} is {:.2}mm from {}, at least {:.2}mm is needed",
+                Shape::Hole(*hole),
+                width,
+                other,
+                minimum
+            ),
+            Problem::NarrowFeature {
+                shape: Shape::Outline,
+                width,
+                minimum,
+            } => write!(
+                f,
+                "outline has material {:.2}mm wide, at least {:.2}mm is needed",
+                width, minimum
+            ),
+            Problem::NarrowFeature {
+                shape,
+                width,
+                minimum,
+            } => write!(
+                f,
+                "{} is {:.2}mm wide, at least {:.2}mm is needed",
+                shape, width, minimum
+            ),
+            Problem::MissingCut { shape, length } => {
+                write!(f, "{} is not cut for {:.2}mm", shape, length)
+            }
+            Problem::NarrowMicroTab { width, minimum } => write!(
+                f,
+                "micro-tab is {:.2}mm wide, at least {:.2}mm is needed",
+                width, minimum
+            ),
+        }
+    }
+}
+
+/// Problem of one part, identified by the part id like `side-panel-2`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub part: String,
+    pub problem: Problem,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.part, self.problem)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ValidationReport {
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl ValidationReport {
+    pub fn is_valid(&self) -> bool {
+        self.diagnostics.is_empty()
+    }
+
+    /// Problems of the part with given id
+    pub fn for_part<'a>(&'a self, id: &'a str) -> impl Iterator<Item = &'a Problem> + 'a {
+        self.diagnostics
+            .iter()
+            .filter(move |diagnostic| diagnostic.part == id)
+            .map(|diagnostic| &diagnostic.problem)
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_valid() {
+            return write!(f, "No problems found");
+        }
+        let lines: Vec<String> = self.diagnostics.iter().map(Diagnostic::to_string).collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+/// Check that parts can be cut as drawn: every shape is closed, parts don't overlap, holes
+/// stay inside their outline with enough material around them, and no slot or piece of
+/// material is too narrow for the kerf. The cuts written to the SVG must run along every
+/// shape, apart from micro-tabs in outlines which are at least as wide as a feature. Cuts of
+/// any part count, so edges shared by common line cutting are cut by one of the parts.
+///
+/// Parts are drawn with horizontal and vertical lines, overlaps and features are measured
+/// along them.
+pub fn validate(parts: &[Part], options: &ValidationOptions) -> ValidationReport {
+    let mut diagnostics = vec![];
+    let cut_lines: Vec<(Point, Point)> = parts
+        .iter()
+        .flat_map(|part| &part.cuts)
+        .filter(|cut| cut.operation.is_cut())
+        .flat_map(|cut| cut.path.segments())
+        .collect();
+
+    for (index, part) in parts.iter().enumerate() {
+        let mut report = |problem| {
+            diagnostics.push(Diagnostic {
+                part: part.id(),
+                problem,
+            })
+        };
+        let shapes = std::iter::once((Shape::Outline, &part.outline)).chain(
+            part.holes
+                .iter()
+                .enumerate()
+                .map(|(hole, polygon)| (Shape::Hole(hole), polygon)),
+        );
+
+        for (shape, polygon) in shapes {
+            if !is_closed(polygon) {
+                report(Problem::OpenPath(shape));
+                continue;
+            }
+            if let Some(width) = narrowest_feature(polygon) {
+                if width < options.minimum_feature() - EPSILON {
+                    report(Problem::NarrowFeature {
+                        shape,
+                        width,
+                        minimum: options.minimum_feature(),
+                    });
+                }
+            }
+            for length in uncut_gaps(polygon, &cut_lines) {
+                let micro_tab = options
+                    .micro_tab_width
+                    .filter(|width| shape == Shape::Outline && length <= width + EPSILON);
+                if micro_tab.is_none() {
+                    report(Problem::MissingCut { shape, length });
+                } else if length < options.minimum_feature() - EPSILON {
+                    report(Problem::NarrowMicroTab {
+                        width: length,
+                        minimum: options.minimum_feature(),
+                    });
+                }
+            }
+        }
+
+        for (hole, polygon) in part.holes.iter().enumerate() {
+            if any_cell(&[polygon, &part.outline], |point| {
+                polygon.contains(point) && !part.outline.contains(point)
+            }) {
+                report(Problem::HoleOutside(hole));
+                continue;
+            }
+
+            let others = std::iter::once((Shape::Outline, &part.outline)).chain(
+                part.holes
+                    .iter()
+                    .enumerate()
+                    .skip(hole + 1)
+                    .map(|(other, polygon)| (Shape::Hole(other), polygon)),
+            );
+            for (other, other_polygon) in others {
+                let width = distance(polygon, other_polygon);
+                if width < options.minimum_web - EPSILON {
+                    report(Problem::ThinWeb {
+                        hole,
+                        other,
+                        width,
+                        minimum: options.minimum_web,
+                    });
+                }
+            }
+        }
+
+        for other in &parts[index + 1..] {
+            if overlap(part, other) {
+                report(Problem::Overlap { other: other.id() });
+            }
+        }
+    }
+
+    ValidationReport { diagnostics }
+}
+
+/// Polygon without zero length lines
+fn corners(polygon: &Polygon) -> Vec<Point> {
+    let mut points: Vec<Point> = vec![];
+    for point in &polygon.points {
+        if points.last().is_none_or(|last| !same_point(*last, *point)) {
+            points.push(*point);
+        }
+    }
+    while points.len() > 1 && same_point(points[0], *points.last().unwrap()) {
+        points.pop();
+    }
+    points
+}
+
+/// Shape has at least three corners, an area and lines which only meet their neighbours
+fn is_closed(polygon: &Polygon) -> bool {
+    let points = corners(polygon);
+    if points.len() < 3 || polygon.area() < EPSILON {
+        return false;
+    }
+
+    let count = points.len();
+    let segment = |i: usize| (points[i], points[(i + 1) % count]);
+    for i in 0..count {
+        for j in i + 2..count {
+            // First and last line are neighbours too
+            if i == 0 && j == count - 1 {
+                continue;
+            }
+            let (a, b) = segment(i);
+            let (c, d) = segment(j);
+            if segments_touch(a, b, c, d) {
+                return false;
+            }
+        }
+    }
+
+    true
+}
+
+/// Lengths of the stretches of the polygon no cut runs along. Stretches meeting at a corner
+/// are one gap.
+fn uncut_gaps(polygon: &Polygon, cut_lines: &[(Point, Point)]) -> Vec<f32> {
+    // Gaps as (start, end, length)
+    let mut gaps: Vec<(Point, Point, f32)> = vec![];
+    for segment in polygon.segments() {
+        for (start, end) in uncut_pieces(segment, cut_lines) {
+            let length = (end.x - start.x).hypot(end.y - start.y);
+            match gaps.last_mut() {
+                Some(gap) if same_point(gap.1, start) => {
+                    gap.1 = end;
+                    gap.2 += length;
+                }
+                _ => gaps.push((start, end, length)),
+            }
+        }
+    }
+    // Gap over the point where the polygon starts
+    if gaps.len() > 1 && same_point(gaps[gaps.len() - 1].1, gaps[0].0) {
+        let (_, _, length) = gaps.pop().expect("there are gaps");
+        gaps[0].2 += length;
+    }
+
+    gaps.into_iter().map(|gap| gap.2).collect()
+}
+
+fn same_point(a: Point, b: Point) -> bool {
+    (a.x - b.x).abs() < EPSILON && (a.y - b.y).abs() < EPSILON
+}
+
+fn cross(a: Point, b: Point, c: Point) -> f32 {
+    (b.x - a.x) * (c.y - a.y) - (b.y - a.y) * (c.x - a.x)
+}
+
+fn within(p: Point, a: Point, b: Point) -> bool {
+    p.x >= a.x.min(b.x) - EPSILON
+        && p.x <= a.x.max(b.x) + EPSILON
+        && p.y >= a.y.min(b.y) - EPSILON
+        && p.y <= a.y.max(b.y) + EPSILON
+}
+
+/// Whether line a-b meets line c-d, touching counts
+fn segments_touch(a: Point, b: Point, c: Point, d: Point) -> bool {
+    let d1 = cross(c, d, a);
+    let d2 = cross(c, d, b);
+    let d3 = cross(a, b, c);
+    let d4 = cross(a, b, d);
+
+    if ((d1 > EPSILON && d2 < -EPSILON) || (d1 < -EPSILON && d2 > EPSILON))
+        && ((d3 > EPSILON && d4 < -EPSILON) || (d3 < -EPSILON && d4 > EPSILON))
+    {
+        return true;
+    }
+
+    (d1.abs() <= EPSILON && within(a, c, d))
+        || (d2.abs() <= EPSILON && within(b, c, d))
+        || (d3.abs() <= EPSILON && within(c, a, b))
+        || (d4.abs() <= EPSILON && within(d, a, b))
+}
+
+fn point_segment_distance(p: Point, a: Point, b: Point) -> f32 {
+    let (dx, dy) = (b.x - a.x, b.y - a.y);
+    let length_squared = dx * dx + dy * dy;
+    let t = if length_squared == 0.0 {
+        0.0
+    } else {
+        (((p.x - a.x) * dx + (p.y - a.y) * dy) / length_squared).clamp(0.0, 1.0)
+    };
+
+    (p.x - (a.x + t * dx)).hypot(p.y - (a.y + t * dy))
+}
+
+/// Shortest distance between the lines of two polygons
+fn distance(a: &Polygon, b: &Polygon) -> f32 {
+    let mut shortest = f32::INFINITY;
+    for (a1, a2) in a.segments() {
+        for (b1, b2) in b.segments() {
+            if segments_touch(a1, a2, b1, b2) {
+                return 0.0;
+            }
+            shortest = shortest
+                .min(point_segment_distance(a1, b1, b2))
+                .min(point_segment_distance(a2, b1, b2))
+                .min(point_segment_distance(b1, a1, a2))
+                .min(point_segment_distance(b2, a1, a2));
+        }
+    }
+    shortest
+}
+
+/// Whether the condition holds in the middle of any cell of the grid through the corners of
+/// given polygons. Cells of a grid through every corner are never crossed by a horizontal or
+/// vertical line of the polygons, so the middle of a cell tells about the whole cell.
+fn any_cell(polygons: &[&Polygon], condition: impl Fn(Point) -> bool) -> bool {
+    let coordinates = |coordinate: fn(&Point) -> f32| {
+        let mut values: Vec<f32> = polygons
+            .iter()
+            .flat_map(|polygon| polygon.points.iter().map(coordinate))
+            .collect();
+        values.sort_by(f32::total_cmp);
+        values.dedup_by(|a, b| (*a - *b).abs() < EPSILON);
+        values
+    };
+    let xs = coordinates(|point| point.x);
+    let ys = coordinates(|point| point.y);
+
+    xs.windows(2).any(|x| {
+        ys.windows(2)
+            .any(|y| condition(Point::new((x[0] + x[1]) / 2.0, (y[0] + y[1]) / 2.0)))
+    })
+}
+
+fn bounds_overlap(a: Rect, b: Rect) -> bool {
+    a.x + a.width > b.x + EPSILON
+        && b.x + b.width > a.x + EPSILON
+        && a.y + a.height > b.y + EPSILON
+        && b.y + b.height > a.y + EPSILON
+}
+
+/// Whether material of the parts overlaps. Parts touching each other, e.g. sharing a common
+/// line, don't overlap.
+fn overlap(a: &Part, b: &Part) -> bool {
+    if !bounds_overlap(a.outline.bounds(), b.outline.bounds()) {
+        return false;
+    }
+
+    let polygons: Vec<&Polygon> = [a, b]
+        .iter()
+        .flat_map(|part| std::iter::once(&part.outline).chain(&part.holes))
+        .collect();
+    any_cell(&polygons, |point| a.contains(point) && b.contains(point))
+}
+
+/// Narrowest distance between opposite lines of the polygon with its inside between them
+fn narrowest_feature(polygon: &Polygon) -> Option<f32> {
+    let segments: Vec<(Point, Point)> = polygon.segments().collect();
+    let vertical: Vec<(f32, f32, f32)> = segments
+        .iter()
+        .filter(|(start, end)| (start.x - end.x).abs() < EPSILON && (start.y - end.y).abs() >= EPSILON)
+        .map(|(start, end)| (start.x, start.y.min(end.y), start.y.max(end.y)))
+        .collect();
+    let horizontal: Vec<(f32, f32, f32)> = segments
+        .iter()
+        .filter(|(start, end)| (start.y - end.y).abs() < EPSILON && (start.x - end.x).abs() >= EPSILON)
+        .map(|(start, end)| (start.y, start.x.min(end.x), start.x.max(end.x)))
+        .collect();
+
+    let across_vertical = narrowest_between(&vertical, |x, y| polygon.contains(Point::new(x, y)));
+    let across_horizontal =
+        narrowest_between(&horizontal, |y, x| polygon.contains(Point::new(x, y)));
+
+    [across_vertical, across_horizontal]
+        .into_iter()
+        .flatten()
+        .reduce(f32::min)
+}
+
+/// Narrowest distance between parallel lines given as (position, from, to) which face each
+/// other with nothing between and the inside of the polygon between them
+fn narrowest_between(
+    lines: &[(f32, f32, f32)],
+    inside: impl Fn(f32, f32) -> bool,
+) -> Option<f32> {
+    let mut narrowest: Option<f32> = None;
+
+    for (i, &(position, from, to)) in lines.iter().enumerate() {
+        for &(other_position, other_from, other_to) in &lines[i + 1..] {
+            let width = (other_position - position).abs();
+            let overlap_from = from.max(other_from);
+            let overlap_to = to.min(other_to);
+            if width < EPSILON || overlap_to - overlap_from < EPSILON {
+                continue;
+            }
+
+            let middle = (overlap_from + overlap_to) / 2.0;
+            let (low, high) = (position.min(other_position), position.max(other_position));
+            let blocked = lines.iter().any(|&(between, from, to)| {
+                between > low + EPSILON && between < high - EPSILON && from <= middle && to >= middle
+            });
+            if !blocked && inside((low + high) / 2.0, middle) {
+                narrowest = Some(narrowest.map_or(width, |narrowest| narrowest.min(width)));
+            }
+        }
+    }
+
+    narrowest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalogue::builtin_containers;
+    use crate::rack::geometry::Polyline;
+    use crate::rack::operations::Operation;
+    use crate::rack::tabs::{add_micro_tabs, MicroTabs};
+    use crate::rack::{generate_svg_with_options, Cut, GenerationOptions, PartKind};
+
+    fn rectangle(x: f32, y: f32, width: f32, height: f32) -> Polygon {
+        Polygon::starting_at(x, y)
+            .vertical_line_to(y + height)
+            .horizontal_line_to(x + width)
+            .vertical_line_to(y)
+    }
+
+    fn cover(x: f32, holes: Vec<Polygon>) -> Part {
+        Part::new(PartKind::Cover, rectangle(x, 0.0, 100.0, 50.0), holes)
+    }
+
+    fn numbered(parts: Vec<Part>) -> Vec<Part> {
+        parts
+            .into_iter()
+            .enumerate()
+            .map(|(i, part)| part.numbered(i + 1))
+            .collect()
+    }
+
+    #[test]
+    fn test_generated_racks_are_valid() {
+        // Default micro-tabs pass the default checks
+        let micro_tabs = MicroTabs::default();
+        let validation = ValidationOptions {
+            micro_tab_width: Some(micro_tabs.width),
+            ..ValidationOptions::default()
+        };
+        for container in builtin_containers() {
+            for (common_line, micro_tabs) in [
+                (false, None),
+                (true, None),
+                (true, Some(micro_tabs.clone())),
+            ] {
+                let options = GenerationOptions {
+                    common_line,
+                    micro_tabs,
+                    ..GenerationOptions::default()
+                };
+                let generated = generate_svg_with_options(3, 2, 4.0, &container, &options);
+
+                let report = validate(&generated.parts, &validation);
+
+                assert!(report.is_valid(), "{}: {}", container.key(), report);
+            }
+        }
+    }
+
+    #[test]
+    fn test_overlapping_parts() {
+        let parts = numbered(vec![cover(0.0, vec![]), cover(100.0, vec![]), cover(150.0, vec![])]);
+
+        let report = validate(&parts, &ValidationOptions::default());
+
+        assert_eq!(
+            report.diagnostics,
+            [Diagnostic {
+                part: "cover-2".to_string(),
+                problem: Problem::Overlap {
+                    other: "cover-3".to_string()
+                }
+            }]
+        );
+    }
+
+    #[test]
+    fn test_holes_outside_and_close_to_edge() {
+        let parts = [cover(
+            0.0,
+            vec![
+                rectangle(90.0, 10.0, 20.0, 4.0),
+                rectangle(10.0, 1.0, 20.0, 4.0),
+                rectangle(10.0, 6.0, 20.0, 4.0),
+            ],
+        )];
+
+        let report = validate(&parts, &ValidationOptions::default());
+        let problems: Vec<&Problem> = report.for_part("cover-1").collect();
+
+        assert_eq!(problems.len(), 3);
+        assert_eq!(problems[0], &Problem::HoleOutside(0));
+        assert!(matches!(
+            problems[1],
+            Problem::ThinWeb { hole: 1, other: Shape::Outline, .. }
+        ));
+        assert!(matches!(
+            problems[2],
+            Problem::ThinWeb { hole: 1, other: Shape::Hole(2), width, .. } if (width - 1.0).abs() < EPSILON
+        ));
+    }
+
+    #[test]
+    fn test_narrow_features() {
+        let thin_tab = Polygon::starting_at(0.0, 0.0)
+            .vertical_line_to(50.0)
+            .horizontal_line_to(40.0)
+            .vertical_line_to(60.0)
+            .horizontal_line_to(40.3)
+            .vertical_line_to(50.0)
+            .horizontal_line_to(100.0)
+            .vertical_line_to(0.0);
+        let parts = [Part::new(
+            PartKind::SidePanel,
+            thin_tab,
+            vec![rectangle(10.0, 10.0, 20.0, 0.5)],
+        )];
+
+        let report = validate(&parts, &ValidationOptions::default());
+
+        assert_eq!(report.diagnostics.len(), 2);
+        assert!(matches!(
+            report.diagnostics[0].problem,
+            Problem::NarrowFeature { shape: Shape::Outline, width, .. } if (width - 0.3).abs() < EPSILON
+        ));
+        assert!(matches!(
+            report.diagnostics[1].problem,
+            Problem::NarrowFeature { shape: Shape::Hole(0), width, .. } if (width - 0.5).abs() < EPSILON
+        ));
+    }
+
+    #[test]
+    fn test_open_paths() {
+        let line = Polygon::starting_at(0.0, 0.0).vertical_line_to(10.0);
+        let crossing = Polygon::starting_at(0.0, 0.0)
+            .vertical_line_to(10.0)
+            .horizontal_line_to(10.0)
+            .vertical_line_to(5.0)
+            .horizontal_line_to(-5.0)
+            .vertical_line_to(0.0);
+        let parts = numbered(vec![
+            Part::new(PartKind::SideWing, line, vec![]),
+            Part::new(PartKind::SideWing, crossing.translate(100.0, 0.0), vec![]),
+        ]);
+
+        let report = validate(&parts, &ValidationOptions::default());
+
+        assert_eq!(report.for_part("side-wing-1").next(), Some(&Problem::OpenPath(Shape::Outline)));
+        assert_eq!(report.for_part("side-wing-2").next(), Some(&Problem::OpenPath(Shape::Outline)));
+    }
+
+    #[test]
+    fn test_cuts_must_trace_shapes() {
+        let mut part = cover(0.0, vec![rectangle(10.0, 10.0, 20.0, 4.0)]);
+        // Hole is cut only on three sides and the outline is left without its closing line
+        part.cuts = vec![
+            Cut {
+                operation: Operation::CutInner,
+                path: Polyline {
+                    points: part.holes[0].points.clone(),
+                    closed: false,
+                },
+            },
+            Cut {
+                operation: Operation::CutOuter,
+                path: Polyline {
+                    points: part.outline.points.clone(),
+                    closed: false,
+                },
+            },
+        ];
+        let options = ValidationOptions {
+            micro_tab_width: Some(0.6),
+            ..ValidationOptions::default()
+        };
+
+        let report = validate(&[part], &options);
+
+        assert_eq!(
+            report.diagnostics.iter().map(|d| &d.problem).collect::<Vec<_>>(),
+            [
+                &Problem::MissingCut {
+                    shape: Shape::Outline,
+                    length: 100.0
+                },
+                &Problem::MissingCut {
+                    shape: Shape::Hole(0),
+                    length: 20.0
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_micro_tabs_are_checked_against_kerf() {
+        let tabbed = |width: f32| {
+            let mut parts = [cover(0.0, vec![])];
+            let tabs = MicroTabs {
+                count: 2,
+                width,
+                parts: vec![PartKind::Cover],
+            };
+            add_micro_tabs(&mut parts, &tabs);
+            parts
+        };
+        let options = ValidationOptions {
+            micro_tab_width: Some(0.5),
+            ..ValidationOptions::default()
+        };
+
+        let report = validate(&tabbed(0.5), &options);
+
+        assert_eq!(report.diagnostics.len(), 2);
+        assert!(report.diagnostics.iter().all(|diagnostic| matches!(
+            diagnostic.problem,
+            Problem::NarrowMicroTab { width, minimum } if (width - 0.5).abs() < EPSILON && (minimum - 0.6).abs() < EPSILON
+        )));
+        // Gaps are missing cuts when micro-tabs are not expected or are narrower than the gaps
+        let without_tabs = validate(&tabbed(0.5), &ValidationOptions::default());
+        assert_eq!(without_tabs.diagnostics.len(), 2);
+        assert!(matches!(without_tabs.diagnostics[0].problem, Problem::MissingCut { .. }));
+        let too_wide = validate(&tabbed(1.0), &options);
+        assert!(matches!(too_wide.diagnostics[0].problem, Problem::MissingCut { .. }));
+
+        let wide_enough = ValidationOptions {
+            micro_tab_width: Some(0.6),
+            ..ValidationOptions::default()
+        };
+        assert!(validate(&tabbed(0.6), &wide_enough).is_valid());
+    }
+}